@@ -0,0 +1,280 @@
+# Deutsche Texte, siehe src/locale/fluent.rs für die unterstützte Syntax
+
+## Spieler
+
+player-green = Grün
+player-orange = Orange
+player-numbered = Spieler { $number }
+player-kind-human = Mensch
+player-kind-computer = Computer
+
+kittens-count = { $count ->
+        [one] { $count } Kätzchen
+       *[other] { $count } Kätzchen
+    }
+cats-count = { $count ->
+        [one] { $count } Katze
+       *[other] { $count } Katzen
+    }
+
+hud-on-board = { $count } auf dem Brett
+hud-booped-off = { $count ->
+        [one] { $count } Figur hinausgeschubst
+       *[other] { $count } Figuren hinausgeschubst
+    }
+hud-returned = { $count } zurückgekommen
+hud-clock = Uhr: { $time }
+
+## Hauptmenü
+
+menu-board-size = Brettgröße
+menu-players = Spieler
+menu-rules = Regeln
+rules-three-kittens = Drei Kätzchen in einer Reihe
+rules-graduation = Erwachsen werden
+menu-time = Bedenkzeit
+time-unlimited = Unbegrenzt
+time-clock = { $minutes } Min. + { $increment } s
+time-per-move = { $seconds } s pro Zug
+menu-series = Match
+series-single = Einzelspiel
+series-best-of = Best of { $games }
+menu-switch-piece-hint = { $key } wechselt zwischen Kätzchen und Katzen
+menu-settings = Einstellungen
+menu-profile = Profil
+menu-profiles = Profile
+menu-leaderboard = Bestenliste
+menu-start = Los!
+menu-tutorial = Spiel lernen
+menu-puzzles = Rätsel
+menu-analysis = Analysebrett
+
+## Pausenmenü
+
+pause-title = Pause
+pause-resume = Weiter
+pause-restart = Neu starten
+pause-analyze = Stellung analysieren
+pause-analyze-warning = Das Analysieren der Stellung beendet dieses Spiel.
+pause-analyze-confirm = Trotzdem analysieren
+pause-analyze-cancel = Weiterspielen
+pause-main-menu = Hauptmenü
+pause-resume-hint = { $key } drücken, um weiterzuspielen
+
+## Einstellungen
+
+settings-title = Einstellungen
+settings-volume = Lautstärke
+settings-mute = Stumm
+settings-unmute = Ton an
+settings-music = Musik
+settings-fullscreen = Vollbild
+settings-on = An
+settings-off = Aus
+settings-resolution = Auflösung
+settings-animation-speed = Animationstempo
+settings-colors = Farben
+settings-piece-markers = Figurenmarker
+settings-language = Sprache
+settings-press-key = Taste drücken...
+settings-back = Zurück
+
+palette-standard = Standard
+palette-deuteranopia = Deuteranopie
+palette-protanopia = Protanopie
+palette-tritanopia = Tritanopie
+palette-high-contrast = Hoher Kontrast
+
+key-restart = Neu starten
+key-pause = Pause
+key-switch-piece = Figur wechseln
+key-confirm = Katze setzen
+key-cancel = Cursor ausblenden
+key-camera = Kameraansicht
+key-overlay = Gefahrenanzeige
+key-mute = Stumm
+key-log = Zugprotokoll
+key-describe = Brett beschreiben
+key-enter-move = Zug eintippen
+key-history = Zugliste
+key-cursor-up = Cursor hoch
+key-cursor-down = Cursor runter
+key-cursor-left = Cursor links
+key-cursor-right = Cursor rechts
+
+## Profile
+
+profiles-title = Profile
+profiles-empty = Nach dem ersten Spiel gibt es hier ein Profil.
+profiles-games = { $count ->
+    [one] Ein Spiel
+   *[other] { $count } Spiele
+}
+profiles-results = { $wins } gewonnen, { $losses } verloren, { $ties } unentschieden
+profiles-average-length = { $moves } Züge pro Spiel
+profiles-boops = { $count } Figuren geschubst
+profiles-cats-lost = { $count } eigene Figuren vom Brett geschubst
+profiles-favorite-opening = Liebster erster Zug: { $cell }
+profiles-achievement-unlocked = { $name }: { $description }
+profiles-achievement-locked = { $name } (gesperrt): { $description }
+
+## Erfolge
+
+achievement-unlocked = { $player } hat „{ $achievement }“ freigeschaltet
+achievement-triple-boop-off = Alle Neune
+achievement-triple-boop-off-description = Mit einem Zug drei Figuren vom Brett schubsen
+achievement-flawless = Makellos
+achievement-flawless-description = Gewinnen, ohne eine Figur vom Brett zu verlieren
+achievement-quick-win = Blitzsieg
+achievement-quick-win-description = In weniger als 10 Zügen gewinnen
+achievement-double-graduation = Jahrgangsbester
+achievement-double-graduation-description = In zwei Zügen hintereinander erwachsen werden
+
+## Bestenliste
+
+leaderboard-title = Bestenliste
+leaderboard-empty = Noch hat niemand eine Wertung.
+leaderboard-rating = { $rating } nach { $games ->
+    [one] einem Spiel
+   *[other] { $games } Spielen
+}
+leaderboard-history = Wertung von { $player } in den letzten Spielen
+
+## Anleitung
+
+tutorial-learner = Du
+tutorial-other = Gegner
+tutorial-step = Schritt { $step } von { $steps }
+tutorial-next = Weiter
+tutorial-finish = Fertig
+tutorial-retry = Nochmal
+tutorial-quit = Hauptmenü
+tutorial-try-again = Das war noch nicht der richtige Zug, versuch es nochmal.
+tutorial-wrong-piece = Falsche Figur! { $key } wechselt zwischen Kätzchen und Katzen, dann versuch es nochmal.
+tutorial-place = Ihr setzt abwechselnd eine eurer Figuren auf ein freies Feld. Klick auf das markierte Feld, um ein Kätzchen zu setzen.
+tutorial-place-done = Gut! Die Kätzchen kommen aus deinem Vorrat neben deinem Namen.
+tutorial-boop = Eine neue Figur schubst alle Nachbarn ein Feld weg. Setz dein Kätzchen neben das andere Kätzchen.
+tutorial-boop-done = Geschubst! Steht dahinter eine andere Figur, bleibt die Figur stehen.
+tutorial-kitten-cat = Erwachsene Katzen sind zu groß für Kätzchen. Setz dein Kätzchen neben die Katze.
+tutorial-kitten-cat-done = Die Katze hat sich nicht bewegt: Kätzchen schubsen nur andere Kätzchen.
+tutorial-cats = Katzen schubsen alles, Kätzchen wie Katzen. { $key } wählt deine Katze, setz sie neben die andere Katze.
+tutorial-cats-done = Nur Katzen sind stark genug, um Katzen zu schubsen.
+tutorial-fall-off = Über den Rand geschubste Figuren fallen vom Brett und gehen zurück an ihren Besitzer. Schubs das Kätzchen vom Brett.
+tutorial-fall-off-done = Weg! Dein Gegner bekommt das Kätzchen zurück und kann es wieder setzen.
+tutorial-graduate = Drei deiner Figuren in einer Reihe werden erwachsen: Sie verlassen das Brett und kommen als Katzen zurück. Bilde eine Reihe aus drei Kätzchen.
+tutorial-graduate-done = Deine Kätzchen sind erwachsen! Jetzt hast du drei Katzen zum Setzen.
+tutorial-win = Drei Katzen in einer Reihe gewinnen das Spiel. { $key } wählt eine Katze, vervollständige die Reihe.
+tutorial-win-done = Gewonnen! Mehr gibt es nicht zu wissen, viel Spaß beim Spielen.
+
+## Rätsel
+
+puzzles-title = Rätsel
+puzzles-solved = { $solved } von { $count } gelöst
+puzzles-win-in = Gewinne in { $moves ->
+    [one] einem Zug
+   *[other] { $moves } Zügen
+} ({ $rules })
+puzzles-done = gelöst
+puzzle-player = Du
+puzzle-defense = Verteidigung
+puzzle-title = Rätsel { $number } von { $count }
+puzzle-goal = Gewinne in höchstens { $moves ->
+    [one] einem Zug
+   *[other] { $moves } Zügen
+}, noch { $left }.
+puzzle-solved = Gelöst!
+puzzle-failed = Die Verteidigung hat gehalten. Nochmal?
+puzzle-next = Nächstes Rätsel
+puzzle-finish = Fertig
+puzzle-retry = Nochmal
+puzzle-quit = Hauptmenü
+
+## Analysebrett
+
+analysis-title = Analysebrett
+analysis-to-move = { $player } am Zug: { $score }
+analysis-winning = gewonnen
+analysis-losing = verloren
+analysis-depth = { $depth ->
+    [one] Einen Zug
+   *[other] { $depth } Züge
+} vorausgeschaut
+analysis-thinking = Schaue { $depth ->
+    [one] einen Zug
+   *[other] { $depth } Züge
+} voraus...
+analysis-move = { $rank }. { $piece } { $cell }: { $score }
+analysis-kitten = Kätzchen
+analysis-cat = Katze
+analysis-tool-play = Spielen
+analysis-tool-kitten = Kätzchen
+analysis-tool-cat = Katzen
+analysis-tool-remove = Entfernen
+analysis-owner = Figuren von
+analysis-next-to-move = { $player } ist dran
+analysis-clear = Brett leeren
+analysis-quit = Hauptmenü
+
+## Spielende
+
+win-winner = { $player } gewinnt!
+win-tie = Unentschieden!
+win-no-more-cats = Keine Katzen mehr!
+win-timeout = { $player } hat die Zeit überschritten
+series-score = { $player } { $wins }
+series-ties = { $count } Unentschieden
+series-winner = { $player } gewinnt das Match!
+series-tied = Das Match endet unentschieden!
+series-next = Spiel { $game } von { $games }, { $player } beginnt
+win-rating = { $player }: { $rating } ({ $change })
+win-restart-hint = { $key } drücken für ein neues Spiel
+
+## Ansagen für Screenreader
+
+log-placed = { $player } setzt { $piece ->
+        [kitten] ein Kätzchen
+       *[cat] eine Katze
+    } auf { $cell }
+log-booped-to = schubst { $piece ->
+        [kitten] das Kätzchen
+       *[cat] die Katze
+    } von { $player } auf { $cell }
+log-booped-off = schubst { $piece ->
+        [kitten] das Kätzchen
+       *[cat] die Katze
+    } von { $player } vom Brett
+log-graduated = Die Figuren von { $player } auf { $first }, { $second } und { $third } werden erwachsen
+log-turn = { $player } ist dran, { $kittens ->
+        [one] ein Kätzchen
+       *[other] { $kittens } Kätzchen
+    } und { $cats ->
+        [one] eine Katze
+       *[other] { $cats } Katzen
+    } übrig
+log-timeout = { $player } hat die Zeit überschritten und scheidet aus
+log-piece = { $piece ->
+        [kitten] Kätzchen
+       *[cat] Katze
+    } von { $player } auf { $cell }
+log-no-pieces = keine Figuren
+log-board = Brett: { $pieces }; { $empty ->
+        [one] ein leeres Feld
+       *[other] { $empty } leere Felder
+    }. { $player } ist dran.
+log-entry-prompt = Feld eintippen, etwa 1,-2, und mit Enter die Katze setzen
+log-entry-line = Zug: { $text }_
+log-entry-invalid = „{ $text }“ ist kein Feld, bitte x,y eingeben
+log-entry-off-board = { $cell } liegt nicht auf dem Brett
+log-entry-taken = { $cell } ist schon besetzt
+
+## Zugliste
+
+history-title = Züge
+history-move = { $number }. { $player } { $piece ->
+        [kitten] Kätzchen
+       *[cat] Katze
+    } { $cell }
+history-boop-to = { $from } → { $to }
+history-boop-off = { $from } raus
+history-viewing = Brett nach Zug { $number }
+history-live = Zurück zum Spiel
@@ -0,0 +1,283 @@
+# English texts, see src/locale/fluent.rs for the supported syntax
+
+## Players
+
+player-green = Green
+player-orange = Orange
+player-numbered = Player { $number }
+player-kind-human = Human
+player-kind-computer = Computer
+
+kittens-count = { $count ->
+        [one] { $count } kitten
+       *[other] { $count } kittens
+    }
+cats-count = { $count ->
+        [one] { $count } cat
+       *[other] { $count } cats
+    }
+
+hud-on-board = { $count } on the board
+hud-booped-off = { $count ->
+        [one] booped off { $count } piece
+       *[other] booped off { $count } pieces
+    }
+hud-returned = { $count } came back
+hud-clock = Clock: { $time }
+
+## Main menu
+
+menu-board-size = Board size
+menu-players = Players
+menu-rules = Rules
+rules-three-kittens = Three kittens in a row
+rules-graduation = Graduation
+menu-time = Time
+time-unlimited = Unlimited
+time-clock = { $minutes } min + { $increment } s
+time-per-move = { $seconds } s per move
+menu-series = Match
+series-single = Single game
+series-best-of = Best of { $games }
+menu-switch-piece-hint = Press { $key } to switch between kittens and cats
+menu-settings = Settings
+menu-profile = Profile
+menu-profiles = Profiles
+menu-leaderboard = Leaderboard
+menu-start = Start
+menu-tutorial = Learn to play
+menu-puzzles = Puzzles
+menu-analysis = Analysis board
+
+## Pause menu
+
+pause-title = Paused
+pause-resume = Resume
+pause-restart = Restart
+pause-analyze = Analyze position
+pause-analyze-warning = Analyzing the position ends this game.
+pause-analyze-confirm = Analyze anyway
+pause-analyze-cancel = Keep playing
+pause-main-menu = Main menu
+pause-resume-hint = Press { $key } to resume
+
+## Settings
+
+settings-title = Settings
+settings-volume = Volume
+settings-mute = Mute
+settings-unmute = Unmute
+settings-music = Music
+settings-fullscreen = Fullscreen
+settings-on = On
+settings-off = Off
+settings-resolution = Resolution
+settings-animation-speed = Animation speed
+settings-colors = Colors
+settings-piece-markers = Piece markers
+settings-language = Language
+settings-press-key = Press a key...
+settings-back = Back
+
+palette-standard = Standard
+palette-deuteranopia = Deuteranopia
+palette-protanopia = Protanopia
+palette-tritanopia = Tritanopia
+palette-high-contrast = High contrast
+
+key-restart = Restart
+key-pause = Pause
+key-switch-piece = Switch piece
+key-confirm = Place cat
+key-cancel = Hide cursor
+key-camera = Camera view
+key-overlay = Threat overlay
+key-mute = Mute
+key-log = Move log
+key-describe = Describe board
+key-enter-move = Type in move
+key-history = Move history
+key-cursor-up = Cursor up
+key-cursor-down = Cursor down
+key-cursor-left = Cursor left
+key-cursor-right = Cursor right
+
+## Profiles
+
+profiles-title = Profiles
+profiles-empty = Finish a game to get a profile.
+profiles-games = { $count ->
+    [one] One game
+   *[other] { $count } games
+}
+profiles-results = { $wins } won, { $losses } lost, { $ties } tied
+profiles-average-length = { $moves } moves per game
+profiles-boops = { $count } pieces booped
+profiles-cats-lost = { $count } own pieces booped off the board
+profiles-favorite-opening = Favorite first move: { $cell }
+profiles-achievement-unlocked = { $name }: { $description }
+profiles-achievement-locked = { $name } (locked): { $description }
+
+## Achievements
+
+achievement-unlocked = { $player } unlocked "{ $achievement }"
+achievement-triple-boop-off = Bowling
+achievement-triple-boop-off-description = Boop three pieces off the board with one move
+achievement-flawless = Flawless
+achievement-flawless-description = Win without losing a piece off the board
+achievement-quick-win = Speedrun
+achievement-quick-win-description = Win in under 10 moves
+achievement-double-graduation = Valedictorian
+achievement-double-graduation-description = Graduate on two moves in a row
+
+## Leaderboard
+
+leaderboard-title = Leaderboard
+leaderboard-empty = Nobody has a rating yet.
+leaderboard-rating = { $rating } after { $games ->
+    [one] one game
+   *[other] { $games } games
+}
+leaderboard-history = Rating of { $player } over the last games
+
+## Tutorial
+
+tutorial-learner = You
+tutorial-other = Opponent
+tutorial-step = Step { $step } of { $steps }
+tutorial-next = Next
+tutorial-finish = Done
+tutorial-retry = Try again
+tutorial-quit = Main menu
+tutorial-try-again = That move didn't do it, try again.
+tutorial-wrong-piece = Wrong piece! Press { $key } to switch between kittens and cats, then try again.
+tutorial-place = Players take turns putting one of their pieces on a free cell. Click the marked cell to place a kitten.
+tutorial-place-done = Nice! Kittens come from your supply, shown next to your name.
+tutorial-boop = A new piece boops every neighbor one cell away from it. Place your kitten next to the other kitten.
+tutorial-boop-done = Boop! A piece doesn't move if another piece is behind it.
+tutorial-kitten-cat = Grown-up cats are too big for kittens to boop. Place your kitten next to the cat.
+tutorial-kitten-cat-done = The cat didn't move: kittens only boop other kittens.
+tutorial-cats = Cats boop everything, kittens and cats alike. Press { $key } to pick your cat and place it next to the other cat.
+tutorial-cats-done = Only cats are strong enough to boop other cats.
+tutorial-fall-off = Pieces booped over the edge fall off the board and go back to their owner. Boop the kitten off the board.
+tutorial-fall-off-done = Gone! Your opponent gets that kitten back to place again.
+tutorial-graduate = Three of your pieces in a row graduate: they leave the board and come back as cats. Make a row of three kittens.
+tutorial-graduate-done = Your kittens grew up! You have three cats to place now.
+tutorial-win = Three cats in a row win the game. Press { $key } to pick a cat and finish the row.
+tutorial-win-done = You won! That's all there is to it, have fun playing.
+
+## Puzzles
+
+puzzles-title = Puzzles
+puzzles-solved = { $solved } of { $count } solved
+puzzles-win-in = Win in { $moves ->
+    [one] one move
+   *[other] { $moves } moves
+} ({ $rules })
+puzzles-done = solved
+puzzle-player = You
+puzzle-defense = Defense
+puzzle-title = Puzzle { $number } of { $count }
+puzzle-goal = Win within { $moves ->
+    [one] one move
+   *[other] { $moves } moves
+}, { $left } left.
+puzzle-solved = Solved!
+puzzle-failed = The defense held. Try again?
+puzzle-next = Next puzzle
+puzzle-finish = Done
+puzzle-retry = Try again
+puzzle-quit = Main menu
+
+## Analysis board
+
+analysis-title = Analysis board
+analysis-to-move = { $player } to move: { $score }
+analysis-winning = winning
+analysis-losing = losing
+analysis-depth = Looked { $depth ->
+    [one] one move
+   *[other] { $depth } moves
+} ahead
+analysis-thinking = Looking { $depth ->
+    [one] one move
+   *[other] { $depth } moves
+} ahead...
+analysis-move = { $rank }. { $piece } { $cell }: { $score }
+analysis-kitten = kitten
+analysis-cat = cat
+analysis-tool-play = Play
+analysis-tool-kitten = Kittens
+analysis-tool-cat = Cats
+analysis-tool-remove = Remove
+analysis-owner = Pieces of
+analysis-next-to-move = { $player } moves
+analysis-clear = Clear board
+analysis-quit = Main menu
+
+## End of the game
+
+win-winner = { $player } won!
+win-tie = Tie!
+win-no-more-cats = No more cats!
+win-timeout = { $player } ran out of time
+series-score = { $player } { $wins }
+series-ties = { $count ->
+        [one] { $count } tie
+       *[other] { $count } ties
+    }
+series-winner = { $player } wins the match!
+series-tied = The match is tied!
+series-next = Game { $game } of { $games }, { $player } starts
+win-rating = { $player }: { $rating } ({ $change })
+win-restart-hint = Press { $key } to restart
+
+## Announcements for screen readers
+
+log-placed = { $player } placed { $piece ->
+        [kitten] a kitten
+       *[cat] a cat
+    } at { $cell }
+log-booped-to = booped { $player } { $piece ->
+        [kitten] kitten
+       *[cat] cat
+    } to { $cell }
+log-booped-off = booped { $player } { $piece ->
+        [kitten] kitten
+       *[cat] cat
+    } off the board
+log-graduated = { $player }'s pieces at { $first }, { $second } and { $third } graduated
+log-turn = { $player }'s turn, { $kittens ->
+        [one] one kitten
+       *[other] { $kittens } kittens
+    } and { $cats ->
+        [one] one cat
+       *[other] { $cats } cats
+    } left
+log-timeout = { $player } ran out of time and is out of the game
+log-piece = { $player } { $piece ->
+        [kitten] kitten
+       *[cat] cat
+    } at { $cell }
+log-no-pieces = no pieces
+log-board = Board: { $pieces }; { $empty ->
+        [one] one empty cell
+       *[other] { $empty } empty cells
+    }. { $player }'s turn.
+log-entry-prompt = Type the cell, like 1,-2, and press enter to place your cat
+log-entry-line = Move: { $text }_
+log-entry-invalid = "{ $text }" is not a cell, use x,y
+log-entry-off-board = { $cell } is not on the board
+log-entry-taken = { $cell } is taken
+
+## Move history
+
+history-title = Moves
+history-move = { $number }. { $player } { $piece ->
+        [kitten] kitten
+       *[cat] cat
+    } { $cell }
+history-boop-to = { $from } → { $to }
+history-boop-off = { $from } off
+history-viewing = Showing the board after move { $number }
+history-live = Back to the game
@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use hexx::Hex;
+use tracing::instrument;
+
+use crate::{events::GridCellClicked, grid::Map, locale::Locale, settings::Settings};
+
+use super::{describe_hex, Announce};
+
+/// Move being typed in as coordinates, like `1,-2`
+#[derive(Debug, Default, Resource)]
+pub struct MoveEntry {
+    pub text: Option<String>,
+}
+
+/// Open the prompt, type the coordinates and press the same key again to
+/// place the cat there
+#[instrument(level = "debug", skip_all)]
+pub fn enter_move(
+    locale: Locale,
+    keys: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    map: Res<Map>,
+    mut entry: ResMut<MoveEntry>,
+    mut typed: EventReader<ReceivedCharacter>,
+    mut clicks: EventWriter<GridCellClicked>,
+    mut announcements: EventWriter<Announce>,
+) {
+    let Some(text) = entry.text.as_mut() else {
+        typed.clear();
+        if keys.just_pressed(settings.keys.enter_move) {
+            entry.text = Some(String::new());
+            announcements.send(Announce(locale.t("log-entry-prompt")));
+        }
+        return;
+    };
+
+    // letters would trigger other key bindings
+    for ReceivedCharacter { char, .. } in typed.iter() {
+        if char.is_ascii_digit() || matches!(char, '-' | ',') {
+            text.push(*char);
+        }
+    }
+    if keys.just_pressed(KeyCode::Back) {
+        text.pop();
+    }
+    if !keys.just_pressed(settings.keys.enter_move) {
+        return;
+    }
+
+    let text = entry.text.take().unwrap_or_default();
+    let Some(hex) = parse_hex(&text) else {
+        announcements.send(Announce(
+            locale.t_args("log-entry-invalid", &[("text", text.into())]),
+        ));
+        return;
+    };
+    let Some(cell) = map.cell_by_hex(hex) else {
+        announcements.send(Announce(
+            locale.t_args("log-entry-off-board", &[("cell", describe_hex(hex).into())]),
+        ));
+        return;
+    };
+    if map.cat_by_hex(hex).is_some() {
+        announcements.send(Announce(
+            locale.t_args("log-entry-taken", &[("cell", describe_hex(hex).into())]),
+        ));
+        return;
+    }
+    debug!(?hex, "Placing cat at typed in cell");
+    clicks.send(GridCellClicked {
+        cell,
+        touch: false,
+        primary: true,
+    });
+}
+
+fn parse_hex(text: &str) -> Option<Hex> {
+    let (x, y) = text.split_once(',')?;
+    Some(Hex::new(x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
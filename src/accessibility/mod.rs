@@ -0,0 +1,394 @@
+//! Moves and board state as text, for players who can't (or don't want to)
+//! follow the 3D board.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use hexx::Hex;
+use tracing::instrument;
+
+use crate::{
+    cats::{Cat, Meowple},
+    events::{CatsGraduated, MoveCat, NewCat, ResetGameEvent, WinEvent},
+    grid::Map,
+    loading::FontAssets,
+    locale::{Arg, Locale},
+    menu::key_bindings_active,
+    players::{PlayerId, Players},
+    settings::Settings,
+    GameState,
+};
+
+mod entry;
+
+pub use entry::MoveEntry;
+
+/// How many announcements are kept around
+const LOG_LENGTH: usize = 50;
+/// How many of them are shown on screen
+const LOG_LINES: usize = 6;
+
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Announce>();
+        app.init_resource::<GameLog>();
+        app.register_type::<GameLog>();
+        app.init_resource::<MoveEntry>();
+
+        app.add_systems(
+            (
+                announce_moves,
+                announce_graduations,
+                announce_results,
+                announce_turns,
+                record_announcements,
+            )
+                .chain()
+                .in_base_set(CoreSet::PostUpdate),
+        );
+        app.add_systems(
+            (
+                toggle_log.run_if(key_bindings_active),
+                describe_board.run_if(key_bindings_active),
+                entry::enter_move,
+            )
+                .in_set(OnUpdate(GameState::Playing)),
+        );
+        app.add_system(show_log.run_if(not(in_state(GameState::Loading))));
+        app.add_system(clear_log.run_if(on_event::<ResetGameEvent>()));
+    }
+}
+
+/// Text to add to the log and read out by screen readers
+#[derive(Debug)]
+pub struct Announce(pub String);
+
+/// What happened in the current game, latest last
+#[derive(Debug, Default, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct GameLog {
+    #[reflect(ignore)]
+    entries: VecDeque<String>,
+    /// Whether the log is shown on screen
+    pub visible: bool,
+}
+
+impl GameLog {
+    pub fn push(&mut self, entry: String) {
+        if self.entries.len() == LOG_LENGTH {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn latest(&self, count: usize) -> impl Iterator<Item = &String> {
+        self.entries
+            .iter()
+            .skip(self.entries.len().saturating_sub(count))
+    }
+}
+
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+struct LogPanel;
+
+/// Axial coordinates of a cell, the way they are typed in to place a cat
+pub fn describe_hex(hex: Hex) -> String {
+    format!("({}, {})", hex.x, hex.y)
+}
+
+/// Piece as it is selected on in the translations
+fn describe_cat(cat: Cat) -> &'static str {
+    match cat {
+        Cat::Kitten => "kitten",
+        Cat::Adult => "cat",
+    }
+}
+
+fn player_name(locale: &Locale, players: &Players, id: PlayerId) -> String {
+    players.by_id(id).map_or_else(
+        || locale.t_args("player-numbered", &[("number", (id.0 + 1).into())]),
+        |player| player.name.clone(),
+    )
+}
+
+/// "Green placed a kitten at (1, -2), booped Orange kitten off the board"
+#[instrument(level = "debug", skip_all)]
+fn announce_moves(
+    locale: Locale,
+    players: Res<Players>,
+    mut new_cats: EventReader<NewCat>,
+    mut moves: EventReader<MoveCat>,
+    mut announcements: EventWriter<Announce>,
+    cats: Query<(&Cat, &PlayerId), With<Meowple>>,
+) {
+    // boops are planned in the same frame the cat is placed
+    let mut parts = new_cats
+        .iter()
+        .map(|new_cat| {
+            locale.t_args(
+                "log-placed",
+                &[
+                    (
+                        "player",
+                        player_name(&locale, &players, new_cat.player).into(),
+                    ),
+                    ("piece", describe_cat(new_cat.cat).into()),
+                    ("cell", describe_hex(new_cat.position).into()),
+                ],
+            )
+        })
+        .collect::<Vec<_>>();
+    for MoveCat { from, to, .. } in moves.iter() {
+        let Ok((cat, owner)) = cats.get(*from) else {
+            continue;
+        };
+        let mut args: Vec<(&str, Arg)> = vec![
+            ("player", player_name(&locale, &players, *owner).into()),
+            ("piece", describe_cat(*cat).into()),
+        ];
+        let id = match to {
+            Some(to) => {
+                args.push(("cell", describe_hex(*to).into()));
+                "log-booped-to"
+            }
+            None => "log-booped-off",
+        };
+        parts.push(locale.t_args(id, &args));
+    }
+    if !parts.is_empty() {
+        announcements.send(Announce(parts.join(", ")));
+    }
+}
+
+fn announce_graduations(
+    locale: Locale,
+    players: Res<Players>,
+    mut graduations: EventReader<CatsGraduated>,
+    mut announcements: EventWriter<Announce>,
+) {
+    for CatsGraduated { player, cells } in graduations.iter() {
+        let [first, second, third]: [Arg; 3] = cells.map(|cell| describe_hex(cell).into());
+        announcements.send(Announce(locale.t_args(
+            "log-graduated",
+            &[
+                ("player", player_name(&locale, &players, *player).into()),
+                ("first", first),
+                ("second", second),
+                ("third", third),
+            ],
+        )));
+    }
+}
+
+fn announce_results(
+    locale: Locale,
+    players: Res<Players>,
+    mut wins: EventReader<WinEvent>,
+    mut announcements: EventWriter<Announce>,
+) {
+    for win in wins.iter() {
+        let text = match win.winner {
+            Some(winner) => locale.t_args(
+                "win-winner",
+                &[("player", player_name(&locale, &players, winner).into())],
+            ),
+            None => format!("{} {}", locale.t("win-tie"), locale.t("win-no-more-cats")),
+        };
+        announcements.send(Announce(text));
+    }
+}
+
+fn announce_turns(
+    locale: Locale,
+    state: Res<State<GameState>>,
+    players: Res<Players>,
+    mut reset: EventReader<ResetGameEvent>,
+    mut announced: Local<Option<PlayerId>>,
+    mut announcements: EventWriter<Announce>,
+) {
+    if reset.iter().count() > 0 {
+        *announced = None;
+    }
+    if state.0 != GameState::Playing {
+        return;
+    }
+    let current = players.current();
+    if *announced == Some(current.id) {
+        return;
+    }
+    *announced = Some(current.id);
+    announcements.send(Announce(locale.t_args(
+        "log-turn",
+        &[
+            ("player", current.name.clone().into()),
+            ("kittens", current.inventory.kittens().into()),
+            ("cats", current.inventory.cats().into()),
+        ],
+    )));
+}
+
+#[instrument(level = "debug", skip_all)]
+fn record_announcements(mut log: ResMut<GameLog>, mut announcements: EventReader<Announce>) {
+    for Announce(text) in announcements.iter() {
+        info!(?text, "Announcement");
+        #[cfg(target_arch = "wasm32")]
+        web::announce(text);
+        log.push(text.clone());
+    }
+}
+
+fn clear_log(mut log: ResMut<GameLog>, mut entry: ResMut<MoveEntry>) {
+    log.entries.clear();
+    *entry = MoveEntry::default();
+}
+
+fn toggle_log(keys: Res<Input<KeyCode>>, settings: Res<Settings>, mut log: ResMut<GameLog>) {
+    if keys.just_pressed(settings.keys.log) {
+        log.visible = !log.visible;
+    }
+}
+
+/// List every piece on the board, for players who can't see it
+#[instrument(level = "debug", skip_all)]
+fn describe_board(
+    locale: Locale,
+    keys: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    map: Res<Map>,
+    players: Res<Players>,
+    mut announcements: EventWriter<Announce>,
+    cats: Query<(&Cat, &PlayerId), With<Meowple>>,
+) {
+    if !keys.just_pressed(settings.keys.describe) {
+        return;
+    }
+
+    let mut pieces = map
+        .cats
+        .iter()
+        .filter_map(|(hex, entity)| Some((*hex, cats.get((*entity)?).ok()?)))
+        .collect::<Vec<_>>();
+    pieces.sort_by_key(|(hex, _)| (hex.x, hex.y));
+    let empty = map.cells.len() - pieces.len();
+
+    let pieces = pieces
+        .into_iter()
+        .map(|(hex, (cat, owner))| {
+            locale.t_args(
+                "log-piece",
+                &[
+                    ("player", player_name(&locale, &players, *owner).into()),
+                    ("piece", describe_cat(*cat).into()),
+                    ("cell", describe_hex(hex).into()),
+                ],
+            )
+        })
+        .collect::<Vec<_>>();
+    let pieces = if pieces.is_empty() {
+        locale.t("log-no-pieces")
+    } else {
+        pieces.join(", ")
+    };
+    announcements.send(Announce(locale.t_args(
+        "log-board",
+        &[
+            ("pieces", pieces.into()),
+            ("empty", empty.into()),
+            ("player", players.current().name.clone().into()),
+        ],
+    )));
+}
+
+/// Latest announcements in the bottom right corner, and the move being typed
+fn show_log(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Locale,
+    log: Res<GameLog>,
+    entry: Res<MoveEntry>,
+    state: Res<State<GameState>>,
+    previous: Query<Entity, With<LogPanel>>,
+) {
+    if !(log.is_changed() || entry.is_changed() || state.is_changed()) {
+        return;
+    }
+    for entity in previous.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let playing = matches!(state.0, GameState::Playing | GameState::GameOver);
+    if !playing || !(log.visible || entry.text.is_some()) {
+        return;
+    }
+
+    let style = TextStyle {
+        font: fonts.fira_sans.clone(),
+        font_size: 16.0,
+        color: Color::BLACK,
+    };
+    let mut lines = if log.visible {
+        log.latest(LOG_LINES).cloned().collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+    if let Some(text) = &entry.text {
+        lines.push(locale.t_args("log-entry-line", &[("text", text.clone().into())]));
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        right: Val::Px(10.),
+                        bottom: Val::Px(10.),
+                        ..default()
+                    },
+                    max_size: Size::width(Val::Px(400.)),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.)),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::WHITE.with_a(0.7)),
+                ..default()
+            },
+            Name::from("Log"),
+            LogPanel,
+        ))
+        .with_children(|parent| {
+            for line in lines {
+                parent.spawn(TextBundle::from_section(line, style.clone()));
+            }
+        });
+}
+
+/// Mirror announcements into the ARIA live region in `index.html`
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use super::LOG_LENGTH;
+
+    pub fn announce(text: &str) {
+        let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+            return;
+        };
+        let Some(region) = document.get_element_by_id("announcements") else {
+            return;
+        };
+        let Ok(entry) = document.create_element("p") else {
+            return;
+        };
+        entry.set_text_content(Some(text));
+        if region.append_child(&entry).is_err() {
+            return;
+        }
+        while region.child_element_count() as usize > LOG_LENGTH {
+            match region.first_element_child() {
+                Some(oldest) => oldest.remove(),
+                None => break,
+            }
+        }
+    }
+}
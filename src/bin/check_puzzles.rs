@@ -0,0 +1,60 @@
+//! Make sure every shipped puzzle can be solved:
+//! `cargo run --bin check_puzzles [file]`
+
+use std::{collections::HashSet, env, fs, process};
+
+use boop::{puzzles::PuzzleSet, rules::Cat};
+use hexx::Hex;
+
+const DEFAULT_PATH: &str = "assets/puzzles/win-in-n.puzzles.ron";
+
+fn main() {
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_PATH.to_string());
+    let puzzles = match fs::read_to_string(&path)
+        .map_err(|error| error.to_string())
+        .and_then(|source| PuzzleSet::parse(&source))
+    {
+        Ok(puzzles) => puzzles,
+        Err(message) => {
+            eprintln!("Cannot read {path}: {message}");
+            process::exit(1);
+        }
+    };
+
+    let mut failed = 0;
+    let mut ids = HashSet::new();
+    for puzzle in &puzzles.puzzles {
+        if !ids.insert(&puzzle.id) {
+            println!("{}: there is another puzzle with this id", puzzle.id);
+            failed += 1;
+        }
+        match puzzle.check() {
+            Ok(solution) => println!("{}: {}", puzzle.id, describe(&solution)),
+            Err(problem) => {
+                println!("{}: {problem}", puzzle.id);
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        eprintln!("{failed} of {} puzzles failed", puzzles.puzzles.len());
+        process::exit(1);
+    }
+}
+
+/// Moves like `kitten 1,-2` in the order they are played
+fn describe(moves: &[(Hex, Cat)]) -> String {
+    moves
+        .iter()
+        .map(|(hex, cat)| {
+            let piece = match cat {
+                Cat::Kitten => "kitten",
+                Cat::Adult => "cat",
+            };
+            format!("{piece} {},{}", hex.x, hex.y)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
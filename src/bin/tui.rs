@@ -0,0 +1,302 @@
+//! Play boop in a terminal: `cargo run --bin tui -- --help`
+
+use std::{
+    env, fs,
+    io::{self, BufRead, Write},
+    process,
+};
+
+use boop::rules::{Cat, Game, MoveReport, Outcome, PlayerId, Rules, SavedGame};
+use hexx::Hex;
+
+const USAGE: &str = "\
+Usage: tui [options]
+
+Options:
+  --players <n>     number of players, 2 to 6 (default 2)
+  --radius <n>      board radius, 2 to 6 (default 3)
+  --graduation      play with the graduation rules (three cats in a row win)
+  --random <seat>   let a random-move computer play this seat (1, 2, ...),
+                    can be given more than once
+  --load <file>     continue a saved game
+";
+
+const HELP: &str = "\
+Commands:
+  x,y          place your kitten (or your cat when out of kittens) on a cell
+  cat x,y      place an adult cat
+  kitten x,y   place a kitten
+  board        show the board
+  coords       show the board with the coordinates of each cell
+  save <file>  save the game
+  load <file>  load a saved game
+  quit         leave the game
+";
+
+struct Options {
+    players: usize,
+    radius: u32,
+    rules: Rules,
+    /// Seats played by the random-move computer
+    random: Vec<PlayerId>,
+    load: Option<String>,
+}
+
+fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    let mut game = match &options.load {
+        Some(path) => match load(path) {
+            Ok(game) => game,
+            Err(message) => {
+                eprintln!("{message}");
+                process::exit(1);
+            }
+        },
+        None => Game::new(options.players, options.rules, options.radius),
+    };
+
+    println!("{HELP}");
+    print_board(&game, false);
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    while game.outcome.is_none() {
+        let player = game.current();
+        if options.random.contains(&player) {
+            let Some((hex, cat)) = game.random_move() else {
+                break;
+            };
+            play(&mut game, hex, cat);
+            continue;
+        }
+
+        print!("{}> ", player_label(player));
+        io::stdout().flush().ok();
+        let Some(Ok(line)) = lines.next() else {
+            return;
+        };
+        let (command, argument) = line
+            .trim()
+            .split_once(' ')
+            .map_or((line.trim(), ""), |(command, argument)| {
+                (command, argument.trim())
+            });
+        match command {
+            "" => {}
+            "help" | "?" => println!("{HELP}"),
+            "quit" | "exit" => return,
+            "board" => print_board(&game, false),
+            "coords" => print_board(&game, true),
+            "save" => match save(&game, argument) {
+                Ok(()) => println!("Saved to {argument}"),
+                Err(message) => println!("{message}"),
+            },
+            "load" => match load(argument) {
+                Ok(loaded) => {
+                    game = loaded;
+                    print_board(&game, false);
+                }
+                Err(message) => println!("{message}"),
+            },
+            "cat" | "kitten" => {
+                let cat = if command == "cat" {
+                    Cat::Adult
+                } else {
+                    Cat::Kitten
+                };
+                match parse_hex(argument) {
+                    Some(hex) => play(&mut game, hex, cat),
+                    None => println!("Expected coordinates like 1,-2"),
+                }
+            }
+            _ => match parse_hex(&line) {
+                Some(hex) => {
+                    let inventory = &game.inventories[player.0 as usize];
+                    let cat = if inventory.has(Cat::Kitten) {
+                        Cat::Kitten
+                    } else {
+                        Cat::Adult
+                    };
+                    play(&mut game, hex, cat);
+                }
+                None => println!("Unknown command, type `help` for a list"),
+            },
+        }
+    }
+
+    match game.outcome {
+        Some(Outcome::Winner(player)) => println!("{} won!", player_label(player)),
+        Some(Outcome::Tie) => println!("Tie! No more cats!"),
+        None => {}
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        players: 2,
+        radius: 3,
+        rules: Rules::ThreeKittens,
+        random: Vec::new(),
+        load: None,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {name}"))
+        };
+        let number = |value: String, range: std::ops::RangeInclusive<usize>| {
+            value
+                .parse::<usize>()
+                .ok()
+                .filter(|number| range.contains(number))
+                .ok_or_else(|| format!("{value} is not in {range:?}"))
+        };
+        match arg.as_str() {
+            "--players" => options.players = number(value(&arg)?, 2..=6)?,
+            "--radius" => options.radius = number(value(&arg)?, 2..=6)? as u32,
+            "--graduation" => options.rules = Rules::Graduation,
+            "--random" => {
+                let seat = number(value(&arg)?, 1..=6)?;
+                options.random.push(PlayerId::new(seat as u8 - 1));
+            }
+            "--load" => options.load = Some(value(&arg)?),
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                process::exit(0);
+            }
+            _ => return Err(format!("Unknown option {arg}")),
+        }
+    }
+    Ok(options)
+}
+
+fn parse_hex(text: &str) -> Option<Hex> {
+    let (x, y) = text.split_once(',')?;
+    Some(Hex::new(x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+fn play(game: &mut Game, hex: Hex, cat: Cat) {
+    let player = game.current();
+    match game.place(hex, cat) {
+        Ok(report) => {
+            print_report(player, hex, cat, &report);
+            print_board(game, false);
+        }
+        Err(error) => println!("Cannot place there: {error}"),
+    }
+}
+
+/// Players are A, B, C... with adult cats in upper case and kittens in lower
+/// case
+fn piece_symbol(cat: Cat, player: PlayerId) -> char {
+    let letter = (b'A' + player.0) as char;
+    match cat {
+        Cat::Kitten => letter.to_ascii_lowercase(),
+        Cat::Adult => letter,
+    }
+}
+
+fn player_label(player: PlayerId) -> String {
+    format!("Player {} ({})", player.0 + 1, (b'A' + player.0) as char)
+}
+
+fn describe_hex(hex: Hex) -> String {
+    format!("{},{}", hex.x, hex.y)
+}
+
+fn print_report(player: PlayerId, hex: Hex, cat: Cat, report: &MoveReport) {
+    let piece = match cat {
+        Cat::Kitten => "kitten",
+        Cat::Adult => "cat",
+    };
+    println!(
+        "{} placed a {piece} at {}",
+        player_label(player),
+        describe_hex(hex)
+    );
+    for boop in &report.boops {
+        match boop.to {
+            Some(to) => println!(
+                "  booped {} to {}",
+                describe_hex(boop.from),
+                describe_hex(to)
+            ),
+            None => println!("  booped {} off the board", describe_hex(boop.from)),
+        }
+    }
+    for (player, row) in &report.graduated {
+        let [a, b, c] = row.map(describe_hex);
+        println!("  {} graduated {a}, {b} and {c}", player_label(*player));
+    }
+}
+
+/// Draw the hexagons with every other row shifted by half a cell, so
+/// neighbors are next to each other
+fn print_board(game: &Game, coords: bool) {
+    let radius = game.radius as i32;
+    let width = if coords { 6 } else { 4 };
+
+    for y in -radius..=radius {
+        let mut line = String::new();
+        for x in -radius..=radius {
+            let hex = Hex::new(x, y);
+            if !game.on_board(hex) {
+                continue;
+            }
+            // doubled coordinates: each step in x is two half cells
+            let column = ((2 * (x + radius) + y) * width / 2) as usize;
+            let text = match (coords, game.pieces.get(&hex)) {
+                (true, _) => describe_hex(hex),
+                (false, Some((cat, owner))) => piece_symbol(*cat, *owner).to_string(),
+                (false, None) => ".".to_string(),
+            };
+            let padding = column.saturating_sub(line.chars().count());
+            line.push_str(&" ".repeat(padding));
+            line.push_str(&format!("{text:^width$}", width = width as usize));
+        }
+        println!("{line}");
+    }
+
+    println!();
+    for (idx, inventory) in game.inventories.iter().enumerate() {
+        let player = PlayerId::new(idx as u8);
+        let marker = if player == game.current() { ">" } else { " " };
+        println!(
+            "{marker} {}: {} kittens, {} cats",
+            player_label(player),
+            inventory.kittens(),
+            inventory.cats()
+        );
+    }
+    println!();
+}
+
+fn save(game: &Game, path: &str) -> Result<(), String> {
+    if path.is_empty() {
+        return Err("Usage: save <file>".to_string());
+    }
+    let content = ron::ser::to_string_pretty(&SavedGame::from(game), Default::default())
+        .map_err(|error| format!("Cannot save game: {error}"))?;
+    fs::write(path, content).map_err(|error| format!("Cannot write {path}: {error}"))
+}
+
+fn load(path: &str) -> Result<Game, String> {
+    if path.is_empty() {
+        return Err("Usage: load <file>".to_string());
+    }
+    let content =
+        fs::read_to_string(path).map_err(|error| format!("Cannot read {path}: {error}"))?;
+    let saved: SavedGame =
+        ron::from_str(&content).map_err(|error| format!("Cannot load {path}: {error}"))?;
+    if saved.inventories.is_empty() {
+        return Err(format!("{path} has no players"));
+    }
+    Ok(saved.into())
+}
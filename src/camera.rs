@@ -0,0 +1,282 @@
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI, TAU};
+
+use bevy::{
+    input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+    prelude::*,
+    window::PrimaryWindow,
+};
+use tracing::instrument;
+
+use crate::{
+    grid::MapSettings,
+    menu::key_bindings_active,
+    players::{PlayerId, Players},
+    settings::Settings,
+};
+
+pub const MIN_DISTANCE: f32 = 15.;
+pub const MAX_DISTANCE: f32 = 120.;
+/// Keep a little angle so the camera never looks at the board edge-on
+const MIN_PITCH: f32 = 0.2;
+/// Looking straight down makes "up" ambiguous
+const MAX_PITCH: f32 = FRAC_PI_2 - 0.01;
+/// Radians per pixel of mouse movement
+const ROTATE_SPEED: f32 = 0.005;
+/// How quickly the camera catches up with its target (per second)
+const SMOOTHING: f32 = 8.;
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<OrbitCamera>();
+        app.init_resource::<CameraView>();
+
+        app.add_systems(
+            (
+                mouse_controls,
+                touch_gestures,
+                switch_preset.run_if(key_bindings_active),
+                apply_preset,
+                smooth_camera,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Position of a camera circling around a point on the board
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, FromReflect)]
+pub struct Orbit {
+    pub focus: Vec3,
+    /// Rotation around the vertical axis, in radians
+    pub yaw: f32,
+    /// Angle above the board, in radians
+    pub pitch: f32,
+    pub distance: f32,
+}
+
+impl Default for Orbit {
+    fn default() -> Self {
+        Self {
+            focus: Vec3::ZERO,
+            yaw: 0.,
+            pitch: FRAC_PI_4,
+            distance: 40. * std::f32::consts::SQRT_2,
+        }
+    }
+}
+
+impl Orbit {
+    pub fn transform(&self) -> Transform {
+        let rotation = Quat::from_euler(EulerRot::YXZ, self.yaw, -self.pitch, 0.);
+        Transform::from_translation(self.focus + rotation * (Vec3::Z * self.distance))
+            .looking_at(self.focus, Vec3::Y)
+    }
+
+    fn lerp(&self, target: &Orbit, t: f32) -> Orbit {
+        Orbit {
+            focus: self.focus.lerp(target.focus, t),
+            yaw: self.yaw + yaw_difference(self.yaw, target.yaw) * t,
+            pitch: self.pitch + (target.pitch - self.pitch) * t,
+            distance: self.distance + (target.distance - self.distance) * t,
+        }
+    }
+}
+
+/// Turn from yaw `from` to `to` the short way around, between -PI and PI
+fn yaw_difference(from: f32, to: f32) -> f32 {
+    (to - from + PI).rem_euclid(TAU) - PI
+}
+
+/// Camera that smoothly follows its target [`Orbit`]
+#[derive(Debug, Clone, Copy, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct OrbitCamera {
+    pub target: Orbit,
+    current: Orbit,
+}
+
+impl OrbitCamera {
+    pub fn transform(&self) -> Transform {
+        self.current.transform()
+    }
+
+    pub fn zoom(&mut self, factor: f32) {
+        self.target.distance = (self.target.distance * factor).clamp(MIN_DISTANCE, MAX_DISTANCE);
+    }
+
+    pub fn rotate(&mut self, yaw: f32, pitch: f32) {
+        self.target.yaw = (self.target.yaw + yaw).rem_euclid(TAU);
+        self.target.pitch = (self.target.pitch + pitch).clamp(MIN_PITCH, MAX_PITCH);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CameraPreset {
+    /// Angled view of the whole board
+    #[default]
+    Overview,
+    TopDown,
+    /// Look from the current player's side of the board, turning with each
+    /// turn
+    Player,
+}
+
+#[derive(Debug, Default, Resource)]
+pub struct CameraView {
+    pub preset: CameraPreset,
+}
+
+/// Drag with the right mouse button to rotate, with the middle button (or
+/// shift and right button) to pan, scroll to zoom
+#[instrument(level = "trace", skip_all)]
+fn mouse_controls(
+    buttons: Res<Input<MouseButton>>,
+    keys: Res<Input<KeyCode>>,
+    settings: Res<MapSettings>,
+    mut motion: EventReader<MouseMotion>,
+    mut wheel: EventReader<MouseWheel>,
+    mut cameras: Query<(&mut OrbitCamera, &Transform)>,
+) {
+    let delta = motion.iter().map(|ev| ev.delta).sum::<Vec2>();
+    let scroll = wheel
+        .iter()
+        .map(|ev| match ev.unit {
+            MouseScrollUnit::Line => ev.y,
+            MouseScrollUnit::Pixel => ev.y / 100.,
+        })
+        .sum::<f32>();
+
+    let shift = keys.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+    let panning =
+        buttons.pressed(MouseButton::Middle) || (shift && buttons.pressed(MouseButton::Right));
+    let rotating = !shift && buttons.pressed(MouseButton::Right);
+
+    // keep the focus on the board
+    let board_radius = settings.board_radius();
+
+    for (mut camera, transform) in cameras.iter_mut() {
+        if rotating && delta != Vec2::ZERO {
+            camera.rotate(-delta.x * ROTATE_SPEED, delta.y * ROTATE_SPEED);
+        }
+        if panning && delta != Vec2::ZERO {
+            let right = Vec3::new(transform.right().x, 0., transform.right().z).normalize_or_zero();
+            let up = Vec3::new(transform.up().x, 0., transform.up().z).normalize_or_zero();
+            let scale = camera.target.distance * 0.002;
+            let focus = camera.target.focus + (up * delta.y - right * delta.x) * scale;
+            camera.target.focus = focus.clamp_length_max(board_radius);
+        }
+        if scroll != 0. {
+            camera.zoom((1. - scroll * 0.1).max(0.1));
+        }
+    }
+}
+
+/// Pinch to zoom, turn two fingers to rotate
+#[instrument(level = "trace", skip_all)]
+fn touch_gestures(touches: Res<Touches>, mut cameras: Query<&mut OrbitCamera>) {
+    let mut pressed = touches.iter();
+    let (Some(a), Some(b), None) = (pressed.next(), pressed.next(), pressed.next()) else {
+        return;
+    };
+
+    let previous = b.previous_position() - a.previous_position();
+    let current = b.position() - a.position();
+    if previous.length() < 1. || current.length() < 1. {
+        return;
+    }
+
+    for mut camera in cameras.iter_mut() {
+        camera.zoom(previous.length() / current.length());
+        // the board turns along with the fingers
+        camera.rotate(previous.angle_between(current), 0.);
+    }
+}
+
+fn switch_preset(keys: Res<Input<KeyCode>>, settings: Res<Settings>, mut view: ResMut<CameraView>) {
+    if !keys.just_pressed(settings.keys.camera) {
+        return;
+    }
+    view.preset = match view.preset {
+        CameraPreset::Overview => CameraPreset::TopDown,
+        CameraPreset::TopDown => CameraPreset::Player,
+        CameraPreset::Player => CameraPreset::Overview,
+    };
+    debug!(preset = ?view.preset, "Switched camera preset");
+}
+
+/// Frame the whole board from the angle of the selected preset
+#[instrument(level = "trace", skip_all)]
+fn apply_preset(
+    view: Res<CameraView>,
+    settings: Res<MapSettings>,
+    players: Res<Players>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut last_player: Local<Option<PlayerId>>,
+    mut cameras: Query<(&mut OrbitCamera, &Projection)>,
+) {
+    let current_player = players.current().id;
+    let player_changed = *last_player != Some(current_player);
+    *last_player = Some(current_player);
+
+    let turn_view = view.preset == CameraPreset::Player && player_changed;
+    if !(view.is_changed() || settings.is_changed() || turn_view) {
+        return;
+    }
+
+    let aspect = windows
+        .get_single()
+        .map_or(1., |window| window.width() / window.height().max(1.));
+
+    for (mut camera, projection) in cameras.iter_mut() {
+        let fov = match projection {
+            Projection::Perspective(projection) => projection.fov,
+            Projection::Orthographic(_) => FRAC_PI_4,
+        };
+        // the board has to fit both vertically and horizontally
+        let half_fov = (fov / 2.).min(((fov / 2.).tan() * aspect).atan());
+        let distance = settings.board_radius() * 1.1 / half_fov.sin();
+
+        let seat = players
+            .players
+            .iter()
+            .position(|p| p.id == current_player)
+            .unwrap_or_default();
+        let (yaw, pitch) = match view.preset {
+            CameraPreset::Overview => (0., FRAC_PI_4),
+            CameraPreset::TopDown => (camera.target.yaw, MAX_PITCH),
+            CameraPreset::Player => (seat as f32 * TAU / players.players.len() as f32, 0.6),
+        };
+
+        camera.target = Orbit {
+            focus: Vec3::ZERO,
+            yaw,
+            pitch,
+            distance: distance.clamp(MIN_DISTANCE, MAX_DISTANCE),
+        };
+    }
+}
+
+fn smooth_camera(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut cameras: Query<(&mut OrbitCamera, &mut Transform)>,
+) {
+    let t = 1. - (-SMOOTHING * settings.animation_speed * time.delta_seconds()).exp();
+    for (mut camera, mut transform) in cameras.iter_mut() {
+        if camera.current == camera.target {
+            continue;
+        }
+        let mut next = camera.current.lerp(&camera.target, t);
+        if (next.focus - camera.target.focus).length() < 0.001
+            && (next.distance - camera.target.distance).abs() < 0.001
+            && yaw_difference(next.yaw, camera.target.yaw).abs() < 0.0001
+            && (next.pitch - camera.target.pitch).abs() < 0.0001
+        {
+            next = camera.target;
+        }
+        camera.current = next;
+        *transform = camera.transform();
+    }
+}
@@ -0,0 +1,264 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::prelude::*;
+use bevy_tweening::{
+    lens::{TransformPositionLens, TransformScaleLens},
+    Animator, EaseFunction, Sequence, Tween, TweenCompleted, Tweenable,
+};
+use tracing::instrument;
+
+use crate::{
+    settings::Settings,
+    sound::{Cue, PlaySound},
+};
+
+/// [`TweenCompleted`] user data for cats that are gone once their animation
+/// is done
+pub const LEFT_BOARD: u64 = 1;
+
+/// How far cats fall after being booped off the board
+const FALL_DEPTH: f32 = 30.;
+/// How high new cats are dropped from
+const DROP_HEIGHT: f32 = 6.;
+/// How high graduating cats float before leaving
+const LIFT_HEIGHT: f32 = 3.;
+
+/// Visual effects waiting to be played one step after another
+///
+/// The game logic updates the board right away and only queues what it did,
+/// so the screen can lag behind without the rules having to wait.
+#[derive(Default, Resource)]
+pub struct AnimationQueue {
+    steps: VecDeque<Step>,
+    /// Time left until the step that is playing is done
+    playing: Option<Timer>,
+}
+
+/// Effects that play at the same time
+#[derive(Debug, Default)]
+struct Step {
+    effects: Vec<(Entity, Effect)>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Effect {
+    /// Newly placed cat drops onto its cell
+    DropIn,
+    /// Booped cat slides to a new position
+    Slide(Vec3),
+    /// Booped cat slides over the edge at this position and falls
+    FallOff(Vec3),
+    /// Cat lights up with this material, floats up and leaves
+    Graduate(Handle<StandardMaterial>),
+}
+
+impl AnimationQueue {
+    /// Play `effects` together, after everything queued before
+    pub fn push(&mut self, effects: Vec<(Entity, Effect)>) {
+        if effects.is_empty() {
+            return;
+        }
+        self.steps.push_back(Step { effects });
+    }
+
+    /// Nothing playing or waiting to be played
+    pub fn is_idle(&self) -> bool {
+        self.playing.is_none() && self.steps.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Start the next step once the current one is done
+///
+/// Runs after the update commands are applied, so cats spawned this frame
+/// can be animated right away.
+#[instrument(level = "debug", skip_all)]
+pub fn play_queue(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut queue: ResMut<AnimationQueue>,
+    mut sounds: EventWriter<PlaySound>,
+    mut commands: Commands,
+    transforms: Query<&Transform>,
+) {
+    if let Some(playing) = queue.playing.as_mut() {
+        if !playing.tick(time.delta()).finished() {
+            return;
+        }
+        queue.playing = None;
+    }
+    let Some(step) = queue.steps.pop_front() else {
+        return;
+    };
+    debug!(effects = step.effects.len(), "Playing animation step");
+
+    let speed = settings.animation_speed;
+    let mut longest = Duration::ZERO;
+    let mut booped = Vec::new();
+    let mut graduated = Vec::new();
+    for (entity, effect) in step.effects {
+        // built now, as earlier steps may have moved the cat
+        let Ok(transform) = transforms.get(entity) else {
+            continue;
+        };
+        let tween = match &effect {
+            Effect::DropIn => drop_in(transform, speed),
+            Effect::Slide(to) => slide(transform.translation, *to, speed),
+            Effect::FallOff(edge) => fall_off(transform.translation, *edge, speed),
+            Effect::Graduate(_) => graduate(transform, speed),
+        };
+        longest = longest.max(tween.duration());
+
+        let mut entity = commands.entity(entity);
+        entity.insert((Animator::new(tween), Visibility::Inherited));
+        match effect {
+            // the tween only starts next frame, don't flash the cat in place
+            Effect::DropIn => {
+                entity.insert(Transform {
+                    translation: transform.translation + Vec3::Y * DROP_HEIGHT,
+                    ..*transform
+                });
+                sounds.send(PlaySound {
+                    cue: Cue::Place,
+                    position: Some(transform.translation),
+                });
+            }
+            Effect::Slide(_) => booped.push(transform.translation),
+            Effect::FallOff(edge) => {
+                booped.push(transform.translation);
+                sounds.send(PlaySound {
+                    cue: Cue::FallOff,
+                    position: Some(edge),
+                });
+            }
+            Effect::Graduate(material) => {
+                entity.insert(material);
+                graduated.push(transform.translation);
+            }
+        }
+    }
+
+    if !booped.is_empty() {
+        sounds.send(PlaySound {
+            cue: Cue::Boop { cats: booped.len() },
+            position: Some(center(&booped)),
+        });
+    }
+    if !graduated.is_empty() {
+        sounds.send(PlaySound {
+            cue: Cue::Graduate,
+            position: Some(center(&graduated)),
+        });
+    }
+    queue.playing = Some(Timer::new(longest, TimerMode::Once));
+}
+
+fn center(positions: &[Vec3]) -> Vec3 {
+    positions.iter().sum::<Vec3>() / positions.len() as f32
+}
+
+pub fn clear_queue(mut queue: ResMut<AnimationQueue>) {
+    queue.clear();
+}
+
+fn duration(millis: u64, speed: f32) -> Duration {
+    Duration::from_millis(millis).div_f32(speed)
+}
+
+/// New cats drop onto their cell and squash a little when landing
+fn drop_in(rest: &Transform, speed: f32) -> Sequence<Transform> {
+    let squashed = rest.scale * Vec3::new(1.2, 0.7, 1.2);
+    Tween::new(
+        EaseFunction::QuadraticIn,
+        duration(250, speed),
+        TransformPositionLens {
+            start: rest.translation + Vec3::Y * DROP_HEIGHT,
+            end: rest.translation,
+        },
+    )
+    .then(Tween::new(
+        EaseFunction::QuadraticOut,
+        duration(80, speed),
+        TransformScaleLens {
+            start: rest.scale,
+            end: squashed,
+        },
+    ))
+    .then(Tween::new(
+        EaseFunction::BackOut,
+        duration(200, speed),
+        TransformScaleLens {
+            start: squashed,
+            end: rest.scale,
+        },
+    ))
+}
+
+fn slide(start: Vec3, end: Vec3, speed: f32) -> Sequence<Transform> {
+    Sequence::from_single(Tween::new(
+        EaseFunction::QuadraticInOut,
+        duration(200, speed),
+        TransformPositionLens { start, end },
+    ))
+}
+
+/// Booped off cats slide over the edge and fall
+fn fall_off(start: Vec3, edge: Vec3, speed: f32) -> Sequence<Transform> {
+    Tween::new(
+        EaseFunction::QuadraticOut,
+        duration(200, speed),
+        TransformPositionLens { start, end: edge },
+    )
+    .then(
+        Tween::new(
+            // accelerating like with gravity
+            EaseFunction::QuadraticIn,
+            duration(600, speed),
+            TransformPositionLens {
+                start: edge,
+                end: edge - Vec3::Y * FALL_DEPTH,
+            },
+        )
+        .with_completed_event(LEFT_BOARD),
+    )
+}
+
+/// Graduating cats float up and shrink away
+fn graduate(start: &Transform, speed: f32) -> Sequence<Transform> {
+    let lifted = start.translation + Vec3::Y * LIFT_HEIGHT;
+    Tween::new(
+        EaseFunction::QuadraticOut,
+        duration(500, speed),
+        TransformPositionLens {
+            start: start.translation,
+            end: lifted,
+        },
+    )
+    .then(
+        Tween::new(
+            EaseFunction::QuadraticIn,
+            duration(250, speed),
+            TransformScaleLens {
+                start: start.scale,
+                end: Vec3::ZERO,
+            },
+        )
+        .with_completed_event(LEFT_BOARD),
+    )
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn despawn_departed(mut commands: Commands, mut completed: EventReader<TweenCompleted>) {
+    for event in completed.iter() {
+        if event.user_data != LEFT_BOARD {
+            continue;
+        }
+        if let Some(entity) = commands.get_entity(event.entity) {
+            debug!(entity = ?event.entity, "Cat left the board");
+            entity.despawn_recursive();
+        }
+    }
+}
@@ -1,17 +1,32 @@
 use bevy::{
     gltf::{Gltf, GltfMesh},
-    prelude::*,
+    prelude::{
+        shape::{self, Capsule, Cube, Torus, UVSphere},
+        *,
+    },
 };
+use hexx::Hex;
+use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
 use crate::{
-    events::NewCat,
+    config::{adult_color, GameConfig},
+    events::{BoardReady, NewCat, ResetGameEvent},
     grid::{GridCell, Map, MapSettings},
     loading::CatModel,
+    modes::StartPosition,
+    players::{PlayerId, Players},
+    settings::Settings,
     GameState,
 };
 
-#[derive(Debug, Clone, Copy, Default, Component, Reflect)]
+use self::animation::{AnimationQueue, Effect};
+
+pub mod animation;
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Component, Reflect, Serialize, Deserialize,
+)]
 #[reflect(Component)]
 pub enum Cat {
     #[default]
@@ -24,6 +39,11 @@ pub enum Cat {
 #[reflect(Component)]
 pub struct Meowple;
 
+/// Shape floating above a cat that tells players apart without colors
+#[derive(Debug, Clone, Copy, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct PieceMarker;
+
 impl Cat {
     pub fn can_boop(&self, other: Cat) -> bool {
         matches!((self, other), (Cat::Adult, _) | (Cat::Kitten, Cat::Kitten))
@@ -37,51 +57,146 @@ impl Plugin for CatPlugin {
         app.register_type::<Cat>();
 
         app.add_startup_system(setup);
+        app.add_system(setup.run_if(on_event::<ResetGameEvent>()));
+        app.add_system(update_materials.run_if(resource_exists_and_changed::<Players>()));
+        app.add_system(show_markers.run_if(resource_changed::<Settings>()));
+        app.init_resource::<AnimationQueue>();
+        app.add_system(animation::play_queue.in_base_set(CoreSet::PostUpdate));
+        app.add_system(animation::clear_queue.run_if(on_event::<ResetGameEvent>()));
+        app.add_system(animation::despawn_departed);
         app.add_system(
             spawn_cats
                 .in_set(OnUpdate(GameState::Playing))
                 .run_if(on_event::<NewCat>()),
         );
+        app.add_system(
+            spawn_start_position
+                .in_base_set(CoreSet::PostUpdate)
+                .run_if(on_event::<BoardReady>()),
+        );
     }
 }
 
 #[derive(Debug, Default, Resource, Reflect)]
 #[reflect(Resource)]
-struct CatAssets {
+pub struct CatAssets {
     /// Material for kittens for the different players
-    kitten_material: [Handle<StandardMaterial>; 2],
+    kitten_material: Vec<Handle<StandardMaterial>>,
     /// Material for adult cats for the different players
-    adult_material: [Handle<StandardMaterial>; 2],
+    adult_material: Vec<Handle<StandardMaterial>>,
+    /// Marker shape for the different players
+    markers: Vec<Handle<Mesh>>,
+    marker_material: Handle<StandardMaterial>,
 }
 
-fn setup(mut commands: Commands, mut materials: ResMut<Assets<StandardMaterial>>) {
+fn setup(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let colors = config.colors();
     commands.insert_resource(CatAssets {
-        kitten_material: [
-            materials.add(StandardMaterial {
-                base_color: Color::LIME_GREEN,
-                ..default()
-            }),
-            materials.add(StandardMaterial {
-                base_color: Color::ORANGE,
-                ..default()
-            }),
-        ],
-        adult_material: [
-            materials.add(StandardMaterial {
-                base_color: Color::DARK_GREEN,
-                ..default()
-            }),
-            materials.add(StandardMaterial {
-                base_color: Color::ORANGE_RED,
-                ..default()
-            }),
-        ],
+        kitten_material: colors
+            .iter()
+            .map(|color| {
+                materials.add(StandardMaterial {
+                    base_color: *color,
+                    ..default()
+                })
+            })
+            .collect(),
+        adult_material: colors
+            .iter()
+            .map(|color| {
+                materials.add(StandardMaterial {
+                    base_color: adult_color(*color),
+                    ..default()
+                })
+            })
+            .collect(),
+        markers: (0..colors.len())
+            .map(|idx| meshes.add(marker_mesh(idx)))
+            .collect(),
+        marker_material: materials.add(StandardMaterial {
+            base_color: Color::rgb(0.1, 0.1, 0.1),
+            unlit: true,
+            ..default()
+        }),
     });
 }
 
+/// Keep cat colors in sync with the player colors, e.g. when the palette changes
+fn update_materials(
+    players: Res<Players>,
+    cat_assets: Res<CatAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (idx, player) in players.players.iter().enumerate() {
+        let colors = [
+            (cat_assets.kitten_material.get(idx), player.color),
+            (
+                cat_assets.adult_material.get(idx),
+                adult_color(player.color),
+            ),
+        ];
+        for (handle, color) in colors {
+            let Some(handle) = handle else { continue };
+            if materials
+                .get(handle)
+                .map_or(false, |m| m.base_color != color)
+            {
+                if let Some(material) = materials.get_mut(handle) {
+                    material.base_color = color;
+                }
+            }
+        }
+    }
+}
+
+/// A different shape for each player slot
+fn marker_mesh(player_idx: usize) -> Mesh {
+    match player_idx % 6 {
+        0 => UVSphere {
+            radius: 0.25,
+            ..default()
+        }
+        .into(),
+        1 => Cube::new(0.4).into(),
+        2 => Torus {
+            radius: 0.25,
+            ring_radius: 0.08,
+            ..default()
+        }
+        .into(),
+        3 => Capsule {
+            radius: 0.12,
+            depth: 0.3,
+            ..default()
+        }
+        .into(),
+        // flat bar
+        4 => shape::Box::new(0.6, 0.1, 0.2).into(),
+        // standing post
+        _ => shape::Box::new(0.15, 0.6, 0.15).into(),
+    }
+}
+
+/// Mesh for the given kind of cat from the cat model
+pub fn cat_mesh(cat: Cat, gltf: &Gltf, assets_gltfmesh: &Assets<GltfMesh>) -> Handle<Mesh> {
+    let idx = match cat {
+        Cat::Kitten => 0,
+        Cat::Adult => 1,
+    };
+    assets_gltfmesh.get(&gltf.meshes[idx]).unwrap().primitives[0]
+        .mesh
+        .clone()
+}
+
 #[instrument(level = "debug", skip_all)]
-fn spawn_cats(
+pub fn spawn_cats(
     settings: Res<MapSettings>,
+    user_settings: Res<Settings>,
     cat_assets: Res<CatAssets>,
     cat_model: Res<CatModel>,
     assets_gltf: Res<Assets<Gltf>>,
@@ -89,6 +204,7 @@ fn spawn_cats(
     mut new_cats: EventReader<NewCat>,
     mut commands: Commands,
     mut map: ResMut<Map>,
+    mut queue: ResMut<AnimationQueue>,
     cells: Query<(&GridCell, &Transform)>,
 ) {
     let gltf = assets_gltf
@@ -102,21 +218,7 @@ fn spawn_cats(
         ..
     } in new_cats.iter()
     {
-        let player_idx = player.0 as usize;
-        let (mesh, material) = match cat {
-            Cat::Kitten => (
-                assets_gltfmesh.get(&gltf.meshes[0]).unwrap().primitives[0]
-                    .mesh
-                    .clone(),
-                cat_assets.kitten_material[player_idx].clone(),
-            ),
-            Cat::Adult => (
-                assets_gltfmesh.get(&gltf.meshes[1]).unwrap().primitives[0]
-                    .mesh
-                    .clone(),
-                cat_assets.adult_material[player_idx].clone(),
-            ),
-        };
+        let mesh = cat_mesh(*cat, gltf, &assets_gltfmesh);
 
         let Some(cell) = map.cell_by_hex(*position) else {
             error!(?position, "Cannot spawn cat at position that is not in map");
@@ -134,28 +236,140 @@ fn spawn_cats(
             }
         };
 
-        let mut transform = *cell_position;
-        // cats should sit on top of the cell
-        transform.translation.y += settings.column_height / 2.;
-        // make cats bigger!
-        transform.scale = Vec3::splat(2.);
+        let new_meople = spawn_meowple(
+            &mut commands,
+            &cat_assets,
+            &user_settings,
+            &settings,
+            mesh,
+            *cell_position,
+            *cell,
+            *cat,
+            *player,
+        );
+        map.add_cat(cell.0, new_meople);
+        queue.push(vec![(new_meople, Effect::DropIn)]);
+    }
+}
 
-        let new_meople = commands
-            .spawn((
+/// Put the pieces of the [`StartPosition`] on the new board, without
+/// animations
+#[instrument(level = "debug", skip_all)]
+pub fn spawn_start_position(
+    settings: Res<MapSettings>,
+    user_settings: Res<Settings>,
+    start_position: Res<StartPosition>,
+    cat_assets: Res<CatAssets>,
+    cat_model: Res<CatModel>,
+    assets_gltf: Res<Assets<Gltf>>,
+    assets_gltfmesh: Res<Assets<GltfMesh>>,
+    mut commands: Commands,
+    mut map: ResMut<Map>,
+    cells: Query<(&GridCell, &Transform)>,
+) {
+    let Some(position) = &start_position.0 else {
+        return;
+    };
+    let gltf = assets_gltf
+        .get(&cat_model.mesh)
+        .expect("cat meshes not done loading!");
+
+    for ((x, y), cat, owner) in &position.pieces {
+        let hex = Hex::new(*x, *y);
+        let Some((cell, cell_position)) =
+            map.cell_by_hex(hex).and_then(|cell| cells.get(cell).ok())
+        else {
+            warn!(?hex, "Start position piece is not on the board");
+            continue;
+        };
+        let meowple = spawn_meowple(
+            &mut commands,
+            &cat_assets,
+            &user_settings,
+            &settings,
+            cat_mesh(*cat, gltf, &assets_gltfmesh),
+            *cell_position,
+            *cell,
+            *cat,
+            PlayerId::new(*owner),
+        );
+        commands.entity(meowple).insert(Visibility::Inherited);
+        map.add_cat(hex, meowple);
+    }
+}
+
+/// Cat figurine with its marker, standing on a cell at `cell_position`
+///
+/// Hidden until its animation shows it.
+fn spawn_meowple(
+    commands: &mut Commands,
+    cat_assets: &CatAssets,
+    user_settings: &Settings,
+    settings: &MapSettings,
+    mesh: Handle<Mesh>,
+    cell_position: Transform,
+    cell: GridCell,
+    cat: Cat,
+    player: PlayerId,
+) -> Entity {
+    let player_idx = player.0 as usize;
+    let material = match cat {
+        Cat::Kitten => cat_assets.kitten_material[player_idx].clone(),
+        Cat::Adult => cat_assets.adult_material[player_idx].clone(),
+    };
+
+    let mut transform = cell_position;
+    // cats should sit on top of the cell
+    transform.translation.y += settings.column_height / 2.;
+    // make cats bigger!
+    transform.scale = Vec3::splat(2.);
+
+    commands
+        .spawn((
+            PbrBundle {
+                mesh,
+                material,
+                transform,
+                // shown once its animation plays
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            Name::from("Kitten"),
+            cell,
+            Meowple,
+            cat,
+            player,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
                 PbrBundle {
-                    mesh,
-                    material,
-                    transform,
+                    mesh: cat_assets.markers[player_idx].clone(),
+                    material: cat_assets.marker_material.clone(),
+                    // above the cat's head, in cat scale
+                    transform: Transform::from_xyz(0., 1.5, 0.),
+                    visibility: marker_visibility(user_settings),
                     ..default()
                 },
-                Name::from("Kitten"),
-                *cell,
-                Meowple,
-                *cat,
-                *player,
-            ))
-            .id();
+                Name::from("Marker"),
+                PieceMarker,
+            ));
+        })
+        .id()
+}
 
-        map.add_cat(cell.0, new_meople);
+fn marker_visibility(settings: &Settings) -> Visibility {
+    if settings.piece_markers {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+fn show_markers(settings: Res<Settings>, mut markers: Query<&mut Visibility, With<PieceMarker>>) {
+    let visibility = marker_visibility(&settings);
+    for mut marker in markers.iter_mut() {
+        if *marker != visibility {
+            *marker = visibility;
+        }
     }
 }
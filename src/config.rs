@@ -0,0 +1,348 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Colors players can pick from in the setup menu (standard palette)
+pub const PLAYER_COLORS: [Color; 6] = [
+    Color::LIME_GREEN,
+    Color::ORANGE,
+    Color::ALICE_BLUE,
+    Color::PURPLE,
+    Color::YELLOW,
+    Color::TURQUOISE,
+];
+
+/// Player colors that can be told apart with deuteranopia (Okabe-Ito)
+pub const DEUTERANOPIA_PLAYER_COLORS: [Color; 6] = [
+    Color::rgb(0.0, 0.447, 0.698),
+    Color::rgb(0.902, 0.624, 0.0),
+    Color::rgb(0.337, 0.706, 0.914),
+    Color::rgb(0.835, 0.369, 0.0),
+    Color::rgb(0.941, 0.894, 0.259),
+    Color::rgb(0.8, 0.475, 0.655),
+];
+
+/// Player colors that can be told apart with protanopia, avoiding reds which
+/// look dark
+pub const PROTANOPIA_PLAYER_COLORS: [Color; 6] = [
+    Color::rgb(0.0, 0.447, 0.698),
+    Color::rgb(0.941, 0.894, 0.259),
+    Color::rgb(0.337, 0.706, 0.914),
+    Color::rgb(0.902, 0.624, 0.0),
+    Color::rgb(0.867, 0.867, 0.867),
+    Color::rgb(0.8, 0.475, 0.655),
+];
+
+/// Player colors that can be told apart with tritanopia, avoiding blue/green
+/// and yellow/violet pairs
+pub const TRITANOPIA_PLAYER_COLORS: [Color; 6] = [
+    Color::rgb(0.835, 0.369, 0.0),
+    Color::rgb(0.0, 0.702, 0.702),
+    Color::rgb(0.961, 0.961, 0.961),
+    Color::rgb(0.8, 0.475, 0.655),
+    Color::rgb(0.498, 0.498, 0.498),
+    Color::rgb(0.545, 0.271, 0.075),
+];
+
+/// Saturated colors that stand out from each other and the board, all light
+/// enough for the darker adult cats to stay visible
+pub const HIGH_CONTRAST_PLAYER_COLORS: [Color; 6] = [
+    Color::rgb(1.0, 1.0, 0.0),
+    Color::rgb(0.0, 0.0, 1.0),
+    Color::rgb(1.0, 0.0, 1.0),
+    Color::rgb(0.0, 1.0, 1.0),
+    Color::rgb(1.0, 0.0, 0.0),
+    Color::rgb(0.0, 1.0, 0.0),
+];
+
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 4;
+
+pub const MIN_MAP_RADIUS: u32 = 2;
+pub const MAX_MAP_RADIUS: u32 = 6;
+
+/// How the next game will be set up
+///
+/// Edited in the main menu and used whenever a game is (re)started.
+#[derive(Debug, Clone, PartialEq, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct GameConfig {
+    pub players: Vec<PlayerConfig>,
+    pub rules: Rules,
+    pub time_control: TimeControl,
+    pub series: SeriesLength,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            players: vec![
+                PlayerConfig {
+                    name: "Green".into(),
+                    color: PLAYER_COLORS[0],
+                    kind: PlayerKind::Human,
+                },
+                PlayerConfig {
+                    name: "Orange".into(),
+                    color: PLAYER_COLORS[1],
+                    kind: PlayerKind::Human,
+                },
+            ],
+            rules: Rules::default(),
+            time_control: TimeControl::default(),
+            series: SeriesLength::default(),
+        }
+    }
+}
+
+impl GameConfig {
+    pub fn add_player(&mut self, palette: Palette) {
+        if self.players.len() >= MAX_PLAYERS {
+            return;
+        }
+        let colors = palette.colors();
+        let color = colors
+            .into_iter()
+            .find(|color| self.players.iter().all(|p| p.color != *color))
+            .unwrap_or(colors[0]);
+        self.players.push(PlayerConfig {
+            name: format!("Player {}", self.players.len() + 1),
+            color,
+            kind: PlayerKind::Human,
+        });
+    }
+
+    pub fn remove_player(&mut self) {
+        if self.players.len() > MIN_PLAYERS {
+            self.players.pop();
+        }
+    }
+
+    /// Kitten color of each player, in player order
+    pub fn colors(&self) -> Vec<Color> {
+        self.players.iter().map(|p| p.color).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Reflect, FromReflect)]
+pub struct PlayerConfig {
+    pub name: String,
+    pub color: Color,
+    pub kind: PlayerKind,
+}
+
+impl PlayerConfig {
+    /// Switch to the next color of the palette
+    pub fn cycle_color(&mut self, palette: Palette) {
+        let colors = palette.colors();
+        let idx = colors
+            .iter()
+            .position(|c| *c == self.color)
+            .map_or(0, |idx| (idx + 1) % colors.len());
+        self.color = colors[idx];
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, FromReflect)]
+pub enum PlayerKind {
+    #[default]
+    Human,
+    /// Places its cats on random free cells
+    Computer,
+}
+
+impl PlayerKind {
+    pub fn toggle(&mut self) {
+        *self = match self {
+            PlayerKind::Human => PlayerKind::Computer,
+            PlayerKind::Computer => PlayerKind::Human,
+        };
+    }
+
+    /// Translation id of the name
+    pub fn label_id(&self) -> &'static str {
+        match self {
+            PlayerKind::Human => "player-kind-human",
+            PlayerKind::Computer => "player-kind-computer",
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, FromReflect, Serialize, Deserialize,
+)]
+pub enum Rules {
+    /// Three kittens in a row win
+    #[default]
+    ThreeKittens,
+    /// Three pieces in a row graduate into adult cats, three adult cats in a
+    /// row win (the original board game rules)
+    Graduation,
+}
+
+impl Rules {
+    pub fn toggle(&mut self) {
+        *self = match self {
+            Rules::ThreeKittens => Rules::Graduation,
+            Rules::Graduation => Rules::ThreeKittens,
+        };
+    }
+
+    /// Translation id of the name
+    pub fn label_id(&self) -> &'static str {
+        match self {
+            Rules::ThreeKittens => "rules-three-kittens",
+            Rules::Graduation => "rules-graduation",
+        }
+    }
+}
+
+/// How much time players have to think
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, FromReflect, Serialize, Deserialize,
+)]
+pub enum TimeControl {
+    #[default]
+    Unlimited,
+    /// Time for the whole game, plus `increment` seconds after each move
+    Clock { minutes: u32, increment: u32 },
+    /// Fixed time for each move
+    PerMove { seconds: u32 },
+}
+
+impl TimeControl {
+    /// Choices in the setup menu
+    pub const PRESETS: [TimeControl; 6] = [
+        TimeControl::Unlimited,
+        TimeControl::Clock {
+            minutes: 3,
+            increment: 2,
+        },
+        TimeControl::Clock {
+            minutes: 5,
+            increment: 0,
+        },
+        TimeControl::Clock {
+            minutes: 10,
+            increment: 5,
+        },
+        TimeControl::PerMove { seconds: 15 },
+        TimeControl::PerMove { seconds: 30 },
+    ];
+
+    pub fn cycle(&mut self) {
+        let idx = Self::PRESETS
+            .iter()
+            .position(|preset| preset == self)
+            .map_or(0, |idx| (idx + 1) % Self::PRESETS.len());
+        *self = Self::PRESETS[idx];
+    }
+}
+
+/// How many games make up a match
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, FromReflect, Serialize, Deserialize,
+)]
+pub enum SeriesLength {
+    #[default]
+    Single,
+    /// Whoever wins more than half of the games wins the match
+    BestOf(u32),
+}
+
+impl SeriesLength {
+    pub fn cycle(&mut self) {
+        *self = match self {
+            SeriesLength::Single => SeriesLength::BestOf(3),
+            SeriesLength::BestOf(3) => SeriesLength::BestOf(5),
+            SeriesLength::BestOf(5) => SeriesLength::BestOf(7),
+            SeriesLength::BestOf(_) => SeriesLength::Single,
+        };
+    }
+
+    pub fn games(&self) -> u32 {
+        match self {
+            SeriesLength::Single => 1,
+            SeriesLength::BestOf(games) => *games,
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, FromReflect, Serialize, Deserialize,
+)]
+pub enum Palette {
+    #[default]
+    Standard,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+    HighContrast,
+}
+
+impl Palette {
+    pub fn colors(&self) -> [Color; 6] {
+        match self {
+            Palette::Standard => PLAYER_COLORS,
+            Palette::Deuteranopia => DEUTERANOPIA_PLAYER_COLORS,
+            Palette::Protanopia => PROTANOPIA_PLAYER_COLORS,
+            Palette::Tritanopia => TRITANOPIA_PLAYER_COLORS,
+            Palette::HighContrast => HIGH_CONTRAST_PLAYER_COLORS,
+        }
+    }
+
+    pub fn cycle(&mut self) {
+        *self = match self {
+            Palette::Standard => Palette::Deuteranopia,
+            Palette::Deuteranopia => Palette::Protanopia,
+            Palette::Protanopia => Palette::Tritanopia,
+            Palette::Tritanopia => Palette::HighContrast,
+            Palette::HighContrast => Palette::Standard,
+        };
+    }
+
+    /// Translation id of the name
+    pub fn label_id(&self) -> &'static str {
+        match self {
+            Palette::Standard => "palette-standard",
+            Palette::Deuteranopia => "palette-deuteranopia",
+            Palette::Protanopia => "palette-protanopia",
+            Palette::Tritanopia => "palette-tritanopia",
+            Palette::HighContrast => "palette-high-contrast",
+        }
+    }
+
+    /// Same slot of `color` in another palette
+    pub fn recolor(&self, color: Color, to: Palette) -> Color {
+        self.colors()
+            .iter()
+            .position(|c| *c == color)
+            .map_or(color, |idx| to.colors()[idx])
+    }
+}
+
+/// Darker shade of a player color used for adult cats
+pub fn adult_color(color: Color) -> Color {
+    let [h, s, l, a] = color.as_hsla_f32();
+    Color::hsla(h, s, l * 0.6, a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adult_cats_stay_visible() {
+        for palette in [
+            Palette::Standard,
+            Palette::Deuteranopia,
+            Palette::Protanopia,
+            Palette::Tritanopia,
+            Palette::HighContrast,
+        ] {
+            for color in palette.colors() {
+                let lightness = adult_color(color).as_hsla_f32()[2];
+                assert!(lightness > 0.1, "{palette:?} has a dark {color:?}");
+            }
+        }
+    }
+}
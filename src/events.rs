@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use bevy_mod_picking::prelude::{Click, ListenedEvent};
+use bevy_mod_picking::prelude::{Click, ListenedEvent, PointerButton};
 use hexx::Hex;
 use tracing::instrument;
 
@@ -11,9 +11,12 @@ impl Plugin for EventsPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<NextPlayer>();
         app.add_event::<ResetGameEvent>();
+        app.add_event::<BoardReady>();
         app.add_event::<NewCat>();
         app.add_event::<MoveCat>();
+        app.add_event::<CatsGraduated>();
         app.add_event::<GridCellClicked>();
+        app.add_event::<PlaceCat>();
         app.add_event::<WinEvent>();
         app.add_event::<OutOfTurns>(); // TODO: trigger this and show gmae over screen
     }
@@ -22,6 +25,10 @@ impl Plugin for EventsPlugin {
 #[derive(Debug)]
 pub struct ResetGameEvent;
 
+/// The grid was (re)built, its cells exist from the next base set on
+#[derive(Debug)]
+pub struct BoardReady;
+
 #[derive(Debug)]
 pub struct NewCat {
     pub player: PlayerId,
@@ -32,22 +39,47 @@ pub struct NewCat {
 
 #[derive(Debug)]
 pub struct MoveCat {
+    /// Player whose new piece caused the boop
+    pub by: PlayerId,
     pub from: Entity,
     pub to: Option<Hex>,
+    /// Direction of the boop
+    pub direction: Hex,
+}
+
+/// Three pieces in a row left the board to come back as cats
+#[derive(Debug)]
+pub struct CatsGraduated {
+    pub player: PlayerId,
+    pub cells: [Hex; 3],
 }
 
 #[derive(Debug)]
 pub struct GridCellClicked {
     pub cell: Entity,
+    /// Taps only select a cell, tapping it again places the cat
+    pub touch: bool,
+    /// Right and middle mouse buttons move the camera instead
+    pub primary: bool,
 }
 
 impl From<ListenedEvent<Click>> for GridCellClicked {
     #[instrument(name = "grid_click", level = "trace", skip_all)]
     fn from(event: ListenedEvent<Click>) -> Self {
-        GridCellClicked { cell: event.target }
+        GridCellClicked {
+            cell: event.target,
+            touch: event.pointer_id.is_touch(),
+            primary: event.button == PointerButton::Primary,
+        }
     }
 }
 
+/// Current player wants to put their selected piece on this cell
+#[derive(Debug)]
+pub struct PlaceCat {
+    pub cell: Entity,
+}
+
 #[derive(Debug)]
 pub struct SwitchPlayerEvent;
 
@@ -57,6 +89,8 @@ pub struct NextPlayer;
 #[derive(Debug)]
 pub struct WinEvent {
     pub winner: Option<PlayerId>,
+    /// Player whose clock ran out, if that ended the game
+    pub timed_out: Option<PlayerId>,
 }
 
 #[derive(Debug)]
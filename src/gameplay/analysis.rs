@@ -0,0 +1,279 @@
+use bevy::{
+    prelude::{shape::Torus, *},
+    utils::HashMap,
+};
+use hexx::Hex;
+use tracing::instrument;
+
+use crate::{
+    cats::{Cat, Meowple},
+    config::{GameConfig, Rules},
+    grid::{Map, MapSettings},
+    players::{Player, PlayerId, Players},
+    settings::Settings,
+    GameState,
+};
+
+use super::{
+    boop::{self, Boop},
+    win::{rows_of_three, winning_cat},
+};
+
+/// Pieces on the board, by cell
+type Pieces = HashMap<Hex, (Cat, PlayerId)>;
+
+/// Whether to mark cells where lines can be completed
+#[derive(Debug, Default, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct AnalysisOverlay {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Mark {
+    /// The current player can complete a line here
+    Opportunity,
+    /// An opponent can complete a line here on their turn
+    Threat,
+}
+
+/// Whether some player can win with their next move
+#[derive(Debug, Default, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct Tension(pub bool);
+
+/// Outline around a marked platform
+#[derive(Debug, Clone, Copy, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct OverlayRing;
+
+pub fn toggle_overlay(
+    keys: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    mut overlay: ResMut<AnalysisOverlay>,
+) {
+    if keys.just_pressed(settings.keys.overlay) {
+        overlay.enabled = !overlay.enabled;
+        info!(enabled = overlay.enabled, "Toggled analysis overlay");
+    }
+}
+
+#[instrument(level = "trace", skip_all)]
+pub fn show_overlay(
+    mut commands: Commands,
+    overlay: Res<AnalysisOverlay>,
+    state: Res<State<GameState>>,
+    config: Res<GameConfig>,
+    settings: Res<MapSettings>,
+    map: Res<Map>,
+    players: Res<Players>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut shown: Local<Vec<(Entity, Mark)>>,
+    cats: Query<(&Cat, &PlayerId), With<Meowple>>,
+    rings: Query<Entity, With<OverlayRing>>,
+) {
+    let marks = if overlay.enabled && state.0 == GameState::Playing {
+        marks(&map, &players, config.rules, |entity| {
+            cats.get(entity).ok().map(|(cat, player)| (*cat, *player))
+        })
+    } else {
+        Vec::new()
+    };
+
+    if *shown == marks {
+        return;
+    }
+    for ring in rings.iter() {
+        commands.entity(ring).despawn_recursive();
+    }
+    *shown = marks.clone();
+    if marks.is_empty() {
+        return;
+    }
+    debug!(marks = marks.len(), "Showing analysis overlay");
+
+    let ring = |radius: f32| Torus {
+        radius,
+        ring_radius: 0.15,
+        ..default()
+    };
+    // threats get the wider ring so both fit on the same cell
+    let opportunity = (
+        meshes.add(ring(settings.cell_size.x).into()),
+        materials.add(StandardMaterial {
+            base_color: Color::GOLD,
+            unlit: true,
+            ..default()
+        }),
+    );
+    let threat = (
+        meshes.add(ring(settings.cell_size.x * 1.2).into()),
+        materials.add(StandardMaterial {
+            base_color: Color::RED,
+            unlit: true,
+            ..default()
+        }),
+    );
+
+    for (cell, mark) in marks {
+        let (mesh, material) = match mark {
+            Mark::Opportunity => opportunity.clone(),
+            Mark::Threat => threat.clone(),
+        };
+        commands.entity(cell).with_children(|parent| {
+            parent.spawn((
+                PbrBundle {
+                    mesh,
+                    material,
+                    // on top of the platform
+                    transform: Transform::from_xyz(0., settings.column_height / 2., 0.),
+                    ..default()
+                },
+                Name::from("Overlay"),
+                OverlayRing,
+            ));
+        });
+    }
+}
+
+#[instrument(level = "trace", skip_all)]
+pub fn update_tension(
+    config: Res<GameConfig>,
+    map: Res<Map>,
+    players: Res<Players>,
+    mut tension: ResMut<Tension>,
+    new_cats: Query<(), Added<Meowple>>,
+    cats: Query<(&Cat, &PlayerId), With<Meowple>>,
+) {
+    // new cats are only in the map until their components are added
+    if !(map.is_changed() || players.is_changed() || !new_cats.is_empty()) {
+        return;
+    }
+    let tense = about_to_win(&map, &players, config.rules, |entity| {
+        cats.get(entity).ok().map(|(cat, player)| (*cat, *player))
+    });
+    if tension.0 != tense {
+        debug!(tense, "Tension changed");
+        tension.0 = tense;
+    }
+}
+
+/// Platforms to mark for the current player
+fn marks(
+    map: &Map,
+    players: &Players,
+    rules: Rules,
+    piece: impl Fn(Entity) -> Option<(Cat, PlayerId)>,
+) -> Vec<(Entity, Mark)> {
+    let pieces = pieces(map, &piece);
+    let cat_kind = |entity| piece(entity).map(|(cat, _)| cat);
+    // winning and graduating lines
+    let counts = |cat: Cat| rules == Rules::Graduation || cat == Cat::Kitten;
+
+    let current = players.current();
+    let opponents = players
+        .players
+        .iter()
+        .filter(|player| player.id != current.id && player.can_do_turn());
+
+    let mut marks = completing_cells(map, &pieces, current, &counts, &cat_kind)
+        .into_iter()
+        .map(|hex| (hex, Mark::Opportunity))
+        .chain(opponents.flat_map(|opponent| {
+            completing_cells(map, &pieces, opponent, &counts, &cat_kind)
+                .into_iter()
+                .map(|hex| (hex, Mark::Threat))
+        }))
+        .filter_map(|(hex, mark)| Some((map.cell_by_hex(hex)?, mark)))
+        .collect::<Vec<_>>();
+    marks.sort();
+    marks.dedup();
+    marks
+}
+
+/// Whether any player can win with their next move
+pub fn about_to_win(
+    map: &Map,
+    players: &Players,
+    rules: Rules,
+    piece: impl Fn(Entity) -> Option<(Cat, PlayerId)>,
+) -> bool {
+    let pieces = pieces(map, &piece);
+    let cat_kind = |entity| piece(entity).map(|(cat, _)| cat);
+    let winning_cat = winning_cat(rules);
+    let counts = |cat: Cat| cat == winning_cat;
+
+    players
+        .players
+        .iter()
+        .filter(|player| player.can_do_turn())
+        .any(|player| !completing_cells(map, &pieces, player, &counts, &cat_kind).is_empty())
+}
+
+fn pieces(map: &Map, piece: &impl Fn(Entity) -> Option<(Cat, PlayerId)>) -> Pieces {
+    map.cats
+        .iter()
+        .filter_map(|(hex, entity)| Some((*hex, piece((*entity)?)?)))
+        .collect()
+}
+
+/// Empty cells where `player` would complete a line of pieces that `counts`
+/// with one of their pieces (one move away from [`rows_of_three`])
+fn completing_cells(
+    map: &Map,
+    pieces: &Pieces,
+    player: &Player,
+    counts: &impl Fn(Cat) -> bool,
+    cat_kind: &impl Fn(Entity) -> Option<Cat>,
+) -> Vec<Hex> {
+    let pieces_to_try = [Cat::Kitten, Cat::Adult]
+        .into_iter()
+        .filter(|cat| player.inventory.has(*cat))
+        .collect::<Vec<_>>();
+
+    map.cells
+        .keys()
+        .filter(|hex| map.cat_by_hex(**hex).is_none())
+        .filter(|hex| {
+            pieces_to_try.iter().any(|cat| {
+                let after = after_move(map, pieces, **hex, *cat, player.id, cat_kind);
+                has_line(&after, player.id, counts)
+            })
+        })
+        .copied()
+        .collect()
+}
+
+/// Pieces after placing `cat` on `hex`, including the boops
+fn after_move(
+    map: &Map,
+    pieces: &Pieces,
+    hex: Hex,
+    cat: Cat,
+    player: PlayerId,
+    cat_kind: &impl Fn(Entity) -> Option<Cat>,
+) -> Pieces {
+    let mut after = pieces.clone();
+    for Boop { from, to, .. } in boop::predict(map, hex, cat, cat_kind) {
+        let piece = after.remove(&from);
+        if let (Some(to), Some(piece)) = (to, piece) {
+            after.insert(to, piece);
+        }
+    }
+    after.insert(hex, (cat, player));
+    after
+}
+
+/// Whether `player` has three pieces in a row that `counts`
+fn has_line(pieces: &Pieces, player: PlayerId, counts: &impl Fn(Cat) -> bool) -> bool {
+    rows_of_three(
+        &pieces
+            .iter()
+            .filter(|(_, (cat, owner))| *owner == player && counts(*cat))
+            .map(|(hex, _)| *hex)
+            .collect::<Vec<_>>(),
+    )
+    .next()
+    .is_some()
+}
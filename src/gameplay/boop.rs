@@ -1,74 +1,73 @@
-use std::time::Duration;
-
 use bevy::prelude::*;
-use bevy_tweening::{lens::TransformPositionLens, Animator, EaseFunction, Tween};
-use hexx::Hex;
+use hexx::{Hex, HexLayout};
 use tracing::instrument;
 
 use crate::{
-    cats::{Cat, Meowple},
+    cats::{
+        animation::{AnimationQueue, Effect},
+        Cat, Meowple,
+    },
     events::{MoveCat, NewCat},
     grid::{GridCell, Map, MapSettings},
-    loading::AudioAssets,
     players::{PlayerId, Players},
+    rules,
 };
 
+/// Cat that gets booped when a piece is placed next to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Boop {
+    pub cat: Entity,
+    pub from: Hex,
+    /// `None` when the cat is booped off the board
+    pub to: Option<Hex>,
+}
+
+/// Boops caused by putting `new_cat` on `position`, without moving any cats
+pub fn predict(
+    map: &Map,
+    position: Hex,
+    new_cat: Cat,
+    cat_kind: impl Fn(Entity) -> Option<Cat>,
+) -> Vec<Boop> {
+    rules::boops(
+        position,
+        new_cat,
+        |hex| map.cell_by_hex(hex).is_some(),
+        |hex| cat_kind(map.cat_by_hex(hex)?),
+    )
+    .into_iter()
+    .filter_map(|rules::Boop { from, to }| {
+        Some(Boop {
+            cat: map.cat_by_hex(from)?,
+            from,
+            to,
+        })
+    })
+    .collect()
+}
+
 #[instrument(level = "debug", skip_all)]
 pub fn plan(
     map: Res<Map>,
-    cells_with_cats: Query<(Entity, &Cat, &PlayerId), With<GridCell>>,
+    cells_with_cats: Query<&Cat, With<GridCell>>,
     mut new_cats: EventReader<NewCat>,
     mut boops: EventWriter<MoveCat>,
 ) {
     for NewCat {
+        player,
         cat: new_cat,
         position,
         ..
     } in new_cats.iter()
     {
-        // find all neighbors
-        // filter out those that are not on the map
-        // filter out those that do not have a cat
-        // filter out those that are not boopable
-        // for each of those, find the cell in the direction of the boop
-        // if that cell is not on the map, cat disappears
-        // else if that cell has a cat, ignore
-        // else move cat to that cell
-
-        let neighbors = Hex::NEIGHBORS_COORDS
-            .into_iter()
-            .map(|direction| (*position + direction, direction));
-        let neighbors_with_cats = neighbors.filter_map(|(cell, direction)| {
-            let entity = map.cat_by_hex(cell)?;
-            let (entity, cat, player) = cells_with_cats.get(entity).ok()?;
-            Some((entity, cat, player, cell, direction))
-        });
-        let boopable_neighbors = neighbors_with_cats
-            .filter(|(_, other_cat, ..)| new_cat.can_boop(**other_cat))
-            // .filter(|(_, _, owner, ..)| player != *owner)
-            ;
-
-        for (boopee, _cat, _cat_owner, boopee_cell, direction) in boopable_neighbors {
-            let possible_boop_destination = boopee_cell + direction;
-
-            match map.cell_by_hex(possible_boop_destination) {
-                Some(_) => {
-                    if map.cat_by_hex(possible_boop_destination).is_some() {
-                        debug!(?possible_boop_destination, "Cannot boop to cell with cat");
-                        continue;
-                    }
-                    boops.send(MoveCat {
-                        from: boopee,
-                        to: Some(possible_boop_destination),
-                    });
-                }
-                None => {
-                    boops.send(MoveCat {
-                        from: boopee,
-                        to: None,
-                    });
-                }
-            };
+        let cat_kind = |entity| cells_with_cats.get(entity).ok().copied();
+        for Boop { cat, from, to } in predict(&map, *position, *new_cat, cat_kind) {
+            boops.send(MoveCat {
+                by: *player,
+                from: cat,
+                to,
+                direction: from - *position,
+            });
         }
     }
 }
@@ -77,20 +76,28 @@ pub fn plan(
 pub fn move_cat(
     mut moves: EventReader<MoveCat>,
     settings: Res<MapSettings>,
-    audio: Res<Audio>,
-    sounds: Res<AudioAssets>,
+    mut queue: ResMut<AnimationQueue>,
     mut players: ResMut<Players>,
     mut map: ResMut<Map>,
     mut commands: Commands,
-    mut cats: Query<(Entity, &GridCell, &Transform, &PlayerId), With<Meowple>>,
+    mut cats: Query<(Entity, &Cat, &GridCell, &Transform, &PlayerId), With<Meowple>>,
     cells: Query<(&Transform,), (With<GridCell>, Without<Meowple>)>,
 ) {
-    let mut any_moves = false;
-    for MoveCat { from, to } in moves.iter() {
-        any_moves = true;
+    let mut effects = Vec::new();
+    let layout = HexLayout {
+        hex_size: settings.cell_size,
+        ..default()
+    };
 
+    for MoveCat {
+        by,
+        from,
+        to,
+        direction,
+    } in moves.iter()
+    {
         debug!(?from, ?to, "Moving cat");
-        let (cat, cat_cell, cat_transform, player_id) = match cats.get_mut(*from) {
+        let (cat, kind, cat_cell, cat_transform, player_id) = match cats.get_mut(*from) {
             Ok(x) => x,
             Err(error) => {
                 error!(entity=?from, ?error, "Cell with cat not found");
@@ -102,8 +109,12 @@ pub fn move_cat(
 
         let Some(to) = *to else {
             debug!(?from, ?to, "Bye bye cat");
-            commands.entity(*from).despawn_recursive();
-            players.gain_kitten(*player_id);
+            let edge = layout.hex_to_world_pos(cat_cell.0 + *direction);
+            let edge = Vec3::new(edge.x, cat_transform.translation.y, edge.y);
+            // not on the board anymore, but still visible while falling
+            commands.entity(*from).remove::<GridCell>();
+            effects.push((cat, Effect::FallOff(edge)));
+            players.booped_off(*by, *player_id, *kind);
             continue;
         };
 
@@ -115,19 +126,8 @@ pub fn move_cat(
 
         let mut new_cat_position = cell_position.translation;
         new_cat_position.y += settings.column_height;
-
-        let tween = Tween::new(
-            EaseFunction::QuadraticInOut,
-            Duration::from_millis(200),
-            TransformPositionLens {
-                start: cat_transform.translation,
-                end: new_cat_position,
-            },
-        );
-        commands.entity(cat).insert(Animator::new(tween));
+        effects.push((cat, Effect::Slide(new_cat_position)));
     }
 
-    if any_moves {
-        audio.play(sounds.boop.clone());
-    }
+    queue.push(effects);
 }
@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use tracing::instrument;
+
+use crate::{
+    accessibility::Announce,
+    cats::animation::AnimationQueue,
+    config::{GameConfig, TimeControl},
+    events::{NextPlayer, WinEvent},
+    locale::Locale,
+    players::{PlayerId, Players},
+    sound::{Cue, PlaySound},
+    GameState,
+};
+
+/// Time left when the "too slow" warning plays
+const WARNING: Duration = Duration::from_secs(10);
+
+/// Time left for each player under the game's [`TimeControl`]
+#[derive(Debug, Default, Resource)]
+pub struct Clocks {
+    time_control: TimeControl,
+    /// By player index
+    remaining: Vec<Duration>,
+    /// By player index
+    timed_out: Vec<bool>,
+    /// Player whose clock is running
+    running: Option<PlayerId>,
+    /// Whether the warning for the running clock was played
+    warned: bool,
+}
+
+impl Clocks {
+    pub fn new(time_control: TimeControl, players: usize) -> Self {
+        let start = match time_control {
+            TimeControl::Unlimited => Duration::ZERO,
+            TimeControl::Clock { minutes, .. } => Duration::from_secs(minutes as u64 * 60),
+            TimeControl::PerMove { seconds } => Duration::from_secs(seconds as u64),
+        };
+        Self {
+            time_control,
+            remaining: vec![start; players],
+            timed_out: vec![false; players],
+            running: None,
+            warned: false,
+        }
+    }
+
+    /// Time `player` has left, `None` without time control
+    pub fn remaining(&self, player: PlayerId) -> Option<Duration> {
+        if self.time_control == TimeControl::Unlimited {
+            return None;
+        }
+        self.remaining.get(player.0 as usize).copied()
+    }
+
+    pub fn is_low(&self, player: PlayerId) -> bool {
+        self.remaining(player)
+            .map_or(false, |remaining| remaining <= WARNING)
+    }
+
+    /// Stop the clock of the player who just moved and start `next`'s
+    fn switch_to(&mut self, next: PlayerId) {
+        if let Some(previous) = self.running {
+            let idx = previous.0 as usize;
+            match self.time_control {
+                TimeControl::Clock { increment, .. } if !self.timed_out[idx] => {
+                    self.remaining[idx] += Duration::from_secs(increment as u64);
+                }
+                _ => {}
+            }
+        }
+        if let TimeControl::PerMove { seconds } = self.time_control {
+            self.remaining[next.0 as usize] = Duration::from_secs(seconds as u64);
+        }
+        self.running = Some(next);
+        self.warned = false;
+    }
+
+    fn players_in_time(&self) -> impl Iterator<Item = PlayerId> + '_ {
+        self.timed_out
+            .iter()
+            .enumerate()
+            .filter(|(_, timed_out)| !**timed_out)
+            .map(|(idx, _)| PlayerId::new(idx as u8))
+    }
+}
+
+pub fn reset_clocks(config: Res<GameConfig>, mut clocks: ResMut<Clocks>) {
+    *clocks = Clocks::new(config.time_control, config.players.len());
+}
+
+/// Run the current player's clock, except while the previous move is still
+/// being animated
+///
+/// A player who runs out of time loses. With more than two players they are
+/// out of the game instead, until only one player is left.
+#[instrument(level = "trace", skip_all)]
+pub fn tick_clocks(
+    time: Res<Time>,
+    locale: Locale,
+    queue: Res<AnimationQueue>,
+    mut clocks: ResMut<Clocks>,
+    mut players: ResMut<Players>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut wins: EventWriter<WinEvent>,
+    mut next_player: EventWriter<NextPlayer>,
+    mut sounds: EventWriter<PlaySound>,
+    mut announcements: EventWriter<Announce>,
+) {
+    if clocks.time_control == TimeControl::Unlimited {
+        return;
+    }
+    let current = players.current().id;
+    let idx = current.0 as usize;
+    if clocks.running != Some(current) {
+        clocks.switch_to(current);
+    }
+    if !queue.is_idle() || clocks.timed_out[idx] {
+        return;
+    }
+
+    let remaining = clocks.remaining[idx].saturating_sub(time.delta());
+    clocks.remaining[idx] = remaining;
+    if remaining <= WARNING && !clocks.warned {
+        clocks.warned = true;
+        sounds.send(Cue::TooSlow.into());
+    }
+    if !remaining.is_zero() {
+        return;
+    }
+
+    info!(player = ?current, "Out of time");
+    clocks.timed_out[idx] = true;
+    let in_time = clocks.players_in_time().collect::<Vec<_>>();
+    if let [winner] = in_time[..] {
+        wins.send(WinEvent {
+            winner: Some(winner),
+            timed_out: Some(current),
+        });
+        next_state.set(GameState::GameOver);
+        return;
+    }
+    announcements.send(Announce(locale.t_args(
+        "log-timeout",
+        &[("player", players.current().name.clone().into())],
+    )));
+    players.forfeit(current);
+    next_player.send(NextPlayer);
+}
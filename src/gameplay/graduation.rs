@@ -0,0 +1,84 @@
+use bevy::{prelude::*, utils::HashMap};
+use hexx::Hex;
+use tracing::instrument;
+
+use crate::{
+    cats::{
+        animation::{AnimationQueue, Effect},
+        Cat, Meowple,
+    },
+    config::{GameConfig, Rules},
+    events::CatsGraduated,
+    grid::{GridCell, Map},
+    players::{PlayerId, Players},
+};
+
+use super::win::rows_of_three;
+
+/// Three pieces of one player in a row (at least one of them a kitten) leave
+/// the board and go back to their owner as adult cats.
+#[instrument(level = "trace", skip_all)]
+pub fn graduate(
+    config: Res<GameConfig>,
+    mut queue: ResMut<AnimationQueue>,
+    mut graduated: EventWriter<CatsGraduated>,
+    mut commands: Commands,
+    mut map: ResMut<Map>,
+    mut players: ResMut<Players>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    cats: Query<(&Cat, &PlayerId, &Handle<StandardMaterial>), With<Meowple>>,
+) {
+    if config.rules != Rules::Graduation {
+        return;
+    }
+
+    // The map is updated right away when cats move, while their components
+    // only catch up when commands are applied.
+    let mut pieces = HashMap::<Hex, (Entity, Cat, PlayerId)>::new();
+    for (hex, entity) in map.cats.iter() {
+        let Some(entity) = *entity else { continue };
+        if let Ok((cat, player, ..)) = cats.get(entity) {
+            pieces.insert(*hex, (entity, *cat, *player));
+        }
+    }
+
+    let cells_by_player = pieces.iter().fold(
+        HashMap::<PlayerId, Vec<Hex>>::new(),
+        |mut map, (hex, (_, _, player))| {
+            map.entry(*player).or_default().push(*hex);
+            map
+        },
+    );
+
+    for (player, cells) in cells_by_player {
+        let Some(row) =
+            rows_of_three(&cells).find(|row| row.iter().any(|hex| pieces[hex].1 == Cat::Kitten))
+        else {
+            continue;
+        };
+
+        info!(?player, ?row, "Cats graduate");
+        let mut effects = Vec::new();
+        for hex in row {
+            let (entity, ..) = pieces[&hex];
+            map.clear_cat_cell(hex);
+            commands.entity(entity).remove::<GridCell>();
+
+            let Ok((.., material)) = cats.get(entity) else {
+                continue;
+            };
+            // glowing in their own color, other cats keep the shared material
+            let glowing = match materials.get(material).cloned() {
+                Some(material) => materials.add(StandardMaterial {
+                    emissive: material.base_color,
+                    ..material
+                }),
+                None => material.clone(),
+            };
+            effects.push((entity, Effect::Graduate(glowing)));
+        }
+        queue.push(effects);
+        players.gain_cats(player, row.len() as u8);
+        graduated.send(CatsGraduated { player, cells: row });
+    }
+}
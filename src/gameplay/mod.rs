@@ -2,16 +2,30 @@ use bevy::prelude::{shape::Cube, *};
 use tracing::instrument;
 
 use crate::{
-    cats::{Cat, Meowple},
-    events::{GridCellClicked, MoveCat, NewCat, NextPlayer, ResetGameEvent, WinEvent},
-    grid::{Hovered, Map},
+    cats::{animation::AnimationQueue, spawn_cats, Cat, Meowple},
+    config::PlayerKind,
+    events::{GridCellClicked, MoveCat, NewCat, NextPlayer, PlaceCat, ResetGameEvent, WinEvent},
+    grid::{GridCursor, Hovered, Map},
+    history::MoveHistory,
+    menu::key_bindings_active,
+    modes::{analysis::clicks_place_cats, normal_game, AllowedCells},
     players::Players,
     GameState,
 };
 
+mod analysis;
 mod boop;
+mod clock;
+mod graduation;
+mod preview;
+mod series;
 mod win;
 
+pub use analysis::Tension;
+pub use clock::Clocks;
+pub use series::Series;
+pub use win::win_screen;
+
 pub struct GamePlayPlugin;
 
 impl Plugin for GamePlayPlugin {
@@ -19,11 +33,19 @@ impl Plugin for GamePlayPlugin {
         app.add_system(setup.in_schedule(OnExit(GameState::Loading)));
         app.add_systems(
             (
-                place_kitten
+                handle_click
                     .run_if(on_event::<GridCellClicked>())
-                    .before(win::win_condition),
+                    .run_if(clicks_place_cats),
+                place_cat
+                    .run_if(on_event::<PlaceCat>())
+                    .before(win::win_condition)
+                    .before(spawn_cats),
                 boop::plan.run_if(on_event::<NewCat>()),
-                boop::move_cat.run_if(on_event::<MoveCat>()),
+                // the new cat drops in before anything gets booped
+                boop::move_cat
+                    .run_if(on_event::<MoveCat>())
+                    .after(spawn_cats),
+                graduation::graduate,
                 win::win_condition.after(reset_game),
                 win::draw_condition.after(reset_game),
             )
@@ -31,7 +53,46 @@ impl Plugin for GamePlayPlugin {
                 .in_set(OnUpdate(GameState::Playing)),
         );
 
-        app.add_system(win::win_screen.run_if(on_event::<WinEvent>()));
+        app.init_resource::<Clocks>();
+        app.add_system(clock::reset_clocks.run_if(on_event::<ResetGameEvent>()));
+        app.add_system(
+            clock::tick_clocks
+                .after(win::draw_condition)
+                .in_set(OnUpdate(GameState::Playing)),
+        );
+
+        app.init_resource::<Series>();
+        app.register_type::<Series>();
+        app.add_system(
+            series::record_result
+                .run_if(on_event::<WinEvent>())
+                .run_if(normal_game)
+                .before(win::win_screen),
+        );
+        app.add_system(series::start_next_game.run_if(on_event::<ResetGameEvent>()));
+        app.add_system(series::reset_series.in_schedule(OnEnter(GameState::Menu)));
+
+        app.init_resource::<analysis::AnalysisOverlay>();
+        app.register_type::<analysis::AnalysisOverlay>();
+        app.register_type::<analysis::OverlayRing>();
+        app.init_resource::<Tension>();
+        app.register_type::<Tension>();
+        app.add_system(analysis::update_tension.in_set(OnUpdate(GameState::Playing)));
+        app.add_system(analysis::toggle_overlay.run_if(key_bindings_active));
+        app.add_system(
+            analysis::show_overlay
+                .after(analysis::toggle_overlay)
+                .run_if(not(in_state(GameState::Loading))),
+        );
+
+        app.register_type::<preview::Ghost>();
+        app.add_system(preview::show_preview.run_if(not(in_state(GameState::Loading))));
+
+        app.add_system(
+            win::win_screen
+                .run_if(not(in_state(GameState::Loading)))
+                .run_if(normal_game),
+        );
         app.add_system(win::win_screen_cleanup.run_if(on_event::<ResetGameEvent>()));
 
         app.add_system(reset_game.run_if(on_event::<ResetGameEvent>()));
@@ -80,11 +141,54 @@ fn reset_game(
     }
 }
 
+/// Clicks only place cats for human players, once the previous move is shown
+/// and while the live game is on screen, on cells the game mode allows
+#[instrument(level = "debug", skip_all)]
+fn handle_click(
+    players: Res<Players>,
+    queue: Res<AnimationQueue>,
+    history: Res<MoveHistory>,
+    map: Res<Map>,
+    allowed: Res<AllowedCells>,
+    mut cursor: ResMut<GridCursor>,
+    mut clicks: EventReader<GridCellClicked>,
+    mut place: EventWriter<PlaceCat>,
+) {
+    if players.current().kind != PlayerKind::Human {
+        debug!("Ignoring click during computer turn");
+        clicks.clear();
+        return;
+    }
+    if !queue.is_idle() {
+        debug!("Ignoring click while animations play");
+        clicks.clear();
+        return;
+    }
+    if history.viewing.is_some() {
+        debug!("Ignoring click while viewing an earlier move");
+        clicks.clear();
+        return;
+    }
+    for ev in clicks.iter().filter(|ev| ev.primary) {
+        let hex = map.cell_by_entity(ev.cell);
+        if !hex.map_or(false, |hex| allowed.allows(hex)) {
+            debug!(?hex, "Ignoring click on a cell that is not allowed");
+            continue;
+        }
+        if ev.touch && cursor.hex != hex {
+            debug!(?hex, "Tap selects cell, tap again to place");
+            cursor.hex = hex;
+            continue;
+        }
+        place.send(PlaceCat { cell: ev.cell });
+    }
+}
+
 #[instrument(level = "debug", skip_all)]
-fn place_kitten(
+fn place_cat(
     mut commands: Commands,
     map: Res<Map>,
-    mut places: EventReader<GridCellClicked>,
+    mut places: EventReader<PlaceCat>,
     mut players: ResMut<Players>,
     mut new_cat: EventWriter<NewCat>,
     mut next_player: EventWriter<NextPlayer>,
@@ -107,15 +211,15 @@ fn place_kitten(
     };
 
     let player = players.current().id;
-    let Some(new_kitten) = players.take_kitten() else {
-        warn!("No more kittens to place");
+    let Some(new_cat_kind) = players.take_piece() else {
+        warn!("No more cats to place");
         next_player.send(NextPlayer);
         return;
     };
 
     new_cat.send(NewCat {
         player,
-        cat: new_kitten,
+        cat: new_cat_kind,
         cell: ev.cell,
         position: hex,
     });
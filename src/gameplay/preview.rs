@@ -0,0 +1,150 @@
+use bevy::{
+    gltf::{Gltf, GltfMesh},
+    prelude::*,
+};
+use hexx::{Hex, HexLayout};
+use tracing::instrument;
+
+use crate::{
+    cats::{animation::AnimationQueue, cat_mesh, Cat, Meowple},
+    config::{adult_color, PlayerKind},
+    grid::{GridCell, GridCursor, Hovered, Map, MapSettings, Platform},
+    history::MoveHistory,
+    loading::CatModel,
+    players::{PlayerId, Players},
+    GameState,
+};
+
+use super::boop::{self, Boop};
+
+/// Opacity of the preview pieces
+const GHOST_ALPHA: f32 = 0.4;
+
+/// Translucent cat that shows what placing a piece would do
+#[derive(Debug, Clone, Copy, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct Ghost;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preview {
+    hex: Hex,
+    piece: Cat,
+    player: PlayerId,
+    boops: Vec<Boop>,
+}
+
+/// Show a ghost of the current player's piece on the hovered cell, and where
+/// the neighboring cats would be booped to
+#[instrument(level = "trace", skip_all)]
+pub fn show_preview(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    map: Res<Map>,
+    settings: Res<MapSettings>,
+    players: Res<Players>,
+    queue: Res<AnimationQueue>,
+    history: Res<MoveHistory>,
+    cursor: Res<GridCursor>,
+    cat_model: Res<CatModel>,
+    assets_gltf: Res<Assets<Gltf>>,
+    assets_gltfmesh: Res<Assets<GltfMesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut shown: Local<Option<Preview>>,
+    hovered: Query<&GridCell, (With<Platform>, With<Hovered>)>,
+    cats: Query<(&Cat, &PlayerId), With<Meowple>>,
+    ghosts: Query<Entity, With<Ghost>>,
+) {
+    let preview = (|| {
+        if state.0 != GameState::Playing
+            || players.current().kind != PlayerKind::Human
+            || !queue.is_idle()
+            || history.viewing.is_some()
+        {
+            return None;
+        }
+        // the keyboard cursor wins over the mouse
+        let hex = cursor
+            .hex
+            .or_else(|| hovered.iter().next().map(|cell| cell.0))?;
+        if map.cell_by_hex(hex).is_none() || map.cat_by_hex(hex).is_some() {
+            return None;
+        }
+        let piece = players.next_piece()?;
+        let cat_kind = |entity| cats.get(entity).ok().map(|(cat, _)| *cat);
+        Some(Preview {
+            hex,
+            piece,
+            player: players.current().id,
+            boops: boop::predict(&map, hex, piece, cat_kind),
+        })
+    })();
+
+    if *shown == preview {
+        return;
+    }
+    for ghost in ghosts.iter() {
+        commands.entity(ghost).despawn_recursive();
+    }
+    *shown = preview.clone();
+    let Some(preview) = preview else {
+        return;
+    };
+    let Some(gltf) = assets_gltf.get(&cat_model.mesh) else {
+        return;
+    };
+    debug!(hex = ?preview.hex, boops = preview.boops.len(), "Showing move preview");
+
+    let layout = HexLayout {
+        hex_size: settings.cell_size,
+        ..default()
+    };
+    // cats sit on top of the cells, see `spawn_cats`
+    let position = |hex: Hex| {
+        let pos = layout.hex_to_world_pos(hex);
+        Vec3::new(pos.x, settings.column_height / 2., pos.y)
+    };
+    let color = |player: PlayerId, cat: Cat| {
+        let color = players.by_id(player).map_or(Color::WHITE, |p| p.color);
+        match cat {
+            Cat::Kitten => color,
+            Cat::Adult => adult_color(color),
+        }
+    };
+
+    let mut spawn_ghost = |cat: Cat, color: Color, translation: Vec3| {
+        commands.spawn((
+            PbrBundle {
+                mesh: cat_mesh(cat, gltf, &assets_gltfmesh),
+                material: materials.add(StandardMaterial {
+                    base_color: color.with_a(GHOST_ALPHA),
+                    alpha_mode: AlphaMode::Blend,
+                    ..default()
+                }),
+                transform: Transform::from_translation(translation).with_scale(Vec3::splat(2.)),
+                ..default()
+            },
+            Name::from("Ghost"),
+            Ghost,
+        ));
+    };
+
+    spawn_ghost(
+        preview.piece,
+        color(preview.player, preview.piece),
+        position(preview.hex),
+    );
+    for Boop { cat, from, to } in preview.boops {
+        let Ok((kind, owner)) = cats.get(cat) else {
+            continue;
+        };
+        match to {
+            Some(to) => spawn_ghost(*kind, color(*owner, *kind), position(to)),
+            // cats falling off the board are shown red, below the edge
+            None => {
+                let beyond = from + (from - preview.hex);
+                let translation = position(beyond) - Vec3::Y * settings.column_height * 2.;
+                spawn_ghost(*kind, Color::RED, translation);
+            }
+        }
+    }
+}
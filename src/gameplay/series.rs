@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+use tracing::instrument;
+
+use crate::{
+    config::{GameConfig, SeriesLength},
+    events::WinEvent,
+    players::PlayerId,
+};
+
+/// Games of the current match, kept when the next game starts
+#[derive(Debug, Default, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct Series {
+    /// Games won, by player index
+    pub wins: Vec<u32>,
+    pub ties: u32,
+    /// Index of the player who starts the next game
+    pub first_player: usize,
+    /// Whether the match is decided, the next game starts a new one
+    pub over: bool,
+}
+
+impl Series {
+    pub fn games_played(&self) -> u32 {
+        self.wins.iter().sum::<u32>() + self.ties
+    }
+
+    /// Player with the most wins, `None` if that's more than one player
+    pub fn leader(&self) -> Option<PlayerId> {
+        let most = *self.wins.iter().max()?;
+        let mut leaders = self
+            .wins
+            .iter()
+            .enumerate()
+            .filter(|(_, wins)| **wins == most);
+        match (leaders.next(), leaders.next()) {
+            (Some((idx, _)), None) => Some(PlayerId::new(idx as u8)),
+            _ => None,
+        }
+    }
+
+    fn record(&mut self, winner: Option<PlayerId>, length: SeriesLength, players: usize) {
+        self.wins.resize(players, 0);
+        match winner {
+            Some(winner) => self.wins[winner.0 as usize] += 1,
+            None => self.ties += 1,
+        }
+        let needed = length.games() / 2 + 1;
+        self.over =
+            self.games_played() >= length.games() || self.wins.iter().any(|wins| *wins >= needed);
+        if length != SeriesLength::Single {
+            self.first_player = (self.first_player + 1) % players;
+        }
+    }
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn record_result(
+    config: Res<GameConfig>,
+    mut series: ResMut<Series>,
+    mut wins: EventReader<WinEvent>,
+) {
+    // a game only ends once
+    let Some(win) = wins.iter().next() else {
+        return;
+    };
+    series.record(win.winner, config.series, config.players.len());
+    info!(wins = ?series.wins, ties = series.ties, over = series.over, "Game finished");
+}
+
+/// Restarting after the match is decided starts a new one, the starting
+/// player keeps alternating
+pub fn start_next_game(config: Res<GameConfig>, mut series: ResMut<Series>) {
+    if !series.over {
+        return;
+    }
+    *series = Series {
+        first_player: series.first_player % config.players.len(),
+        ..default()
+    };
+}
+
+/// Leaving for the main menu gives up the match
+pub fn reset_series(mut series: ResMut<Series>) {
+    *series = Series::default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GREEN: PlayerId = PlayerId(0);
+    const ORANGE: PlayerId = PlayerId(1);
+
+    #[test]
+    fn best_of_three_ends_after_two_wins() {
+        let mut series = Series::default();
+        series.record(Some(GREEN), SeriesLength::BestOf(3), 2);
+        assert!(!series.over);
+        assert_eq!(series.leader(), Some(GREEN));
+        series.record(Some(GREEN), SeriesLength::BestOf(3), 2);
+        assert!(series.over);
+        assert_eq!(series.wins, vec![2, 0]);
+    }
+
+    #[test]
+    fn ties_count_as_games() {
+        let mut series = Series::default();
+        series.record(Some(GREEN), SeriesLength::BestOf(3), 2);
+        series.record(Some(ORANGE), SeriesLength::BestOf(3), 2);
+        assert_eq!(series.leader(), None);
+        series.record(None, SeriesLength::BestOf(3), 2);
+        assert!(series.over);
+        assert_eq!(series.games_played(), 3);
+        assert_eq!(series.leader(), None);
+    }
+
+    #[test]
+    fn starting_player_alternates_within_a_match() {
+        let mut series = Series::default();
+        series.record(Some(GREEN), SeriesLength::BestOf(5), 3);
+        assert_eq!(series.first_player, 1);
+        series.record(None, SeriesLength::BestOf(5), 3);
+        series.record(None, SeriesLength::BestOf(5), 3);
+        assert_eq!(series.first_player, 0);
+
+        let mut single = Series::default();
+        single.record(Some(ORANGE), SeriesLength::Single, 2);
+        assert!(single.over);
+        assert_eq!(single.first_player, 0);
+    }
+}
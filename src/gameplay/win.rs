@@ -4,14 +4,21 @@ use hexx::Hex;
 use tracing::instrument;
 
 use crate::{
-    cats::Cat,
+    cats::{animation::AnimationQueue, Cat, Meowple},
+    config::{GameConfig, SeriesLength},
     events::{ResetGameEvent, WinEvent},
-    grid::GridCell,
+    grid::Map,
     loading::FontAssets,
+    locale::Locale,
     players::{PlayerId, Players},
+    profiles::RatingChanges,
+    settings::Settings,
+    sound::{Cue, PlaySound},
     GameState,
 };
 
+use super::Series;
+
 #[instrument(level = "trace", skip_all)]
 pub fn draw_condition(
     players: Res<Players>,
@@ -24,34 +31,43 @@ pub fn draw_condition(
         return;
     }
     if !players.players.iter().any(|player| player.can_do_turn()) {
-        wins.send(WinEvent { winner: None });
+        wins.send(WinEvent {
+            winner: None,
+            timed_out: None,
+        });
         next_state.set(GameState::GameOver);
     }
 }
 
-/// A player wins if they have three adult cats in a row.
+/// A player wins if they have three adult cats in a row (or three kittens,
+/// depending on the rules).
 #[instrument(level = "trace", skip_all)]
 pub fn win_condition(
+    config: Res<GameConfig>,
     mut next_state: ResMut<NextState<GameState>>,
     mut reset: EventReader<ResetGameEvent>,
     mut wins: EventWriter<WinEvent>,
-    cats: Query<(&Cat, &PlayerId, &GridCell)>,
+    map: Res<Map>,
+    cats: Query<(&Cat, &PlayerId), With<Meowple>>,
 ) {
     // dedup
     if reset.iter().count() > 0 {
         return;
     }
 
-    let cat_cells_by_player = cats
-        .iter()
-        .filter(|(cat, ..)| matches!(**cat, Cat::Kitten)) // FIXME: real cats
-        .fold(
-            HashMap::<PlayerId, Vec<Hex>>::new(),
-            |mut map, (_cat, player, cell)| {
-                map.entry(*player).or_default().push(cell.0);
-                map
-            },
-        );
+    // Like graduation, go by the map: the cats' components only catch up
+    // with boops and graduations when commands are applied.
+    let winning_cat = winning_cat(config.rules);
+    let mut cat_cells_by_player = HashMap::<PlayerId, Vec<Hex>>::new();
+    for (hex, entity) in map.cats.iter() {
+        let Some(entity) = *entity else { continue };
+        match cats.get(entity) {
+            Ok((cat, player)) if *cat == winning_cat => {
+                cat_cells_by_player.entry(*player).or_default().push(*hex);
+            }
+            _ => {}
+        }
+    }
 
     for (player, cats) in cat_cells_by_player {
         if cats.len() < 3 {
@@ -59,35 +75,22 @@ pub fn win_condition(
             continue;
         }
 
-        for cat in &cats {
-            for direction in hexx::Direction::iter() {
-                let mut count = 1;
-                let mut hex = cat.neighbor(direction);
-                while cats.contains(&hex) {
-                    count += 1;
-                    hex = hex.neighbor(direction);
-                }
-                if count > 2 {
-                    info!("Player {player} wins!");
-                    wins.send(WinEvent {
-                        winner: Some(player),
-                    });
-                    next_state.set(GameState::GameOver);
-                    return;
-                }
-                trace!(
-                    ?player,
-                    ?cat,
-                    ?direction,
-                    count,
-                    "not enough cats in this row"
-                );
-            }
+        if rows_of_three(&cats).next().is_some() {
+            info!("Player {player} wins!");
+            wins.send(WinEvent {
+                winner: Some(player),
+                timed_out: None,
+            });
+            next_state.set(GameState::GameOver);
+            return;
         }
+        trace!(?player, "not enough cats in a row");
     }
     trace!("no winner yet");
 }
 
+pub use crate::rules::{rows_of_three, winning_cat};
+
 #[derive(Debug, Default, Component, Reflect)]
 #[reflect(Component)]
 pub struct WinScreen;
@@ -95,12 +98,43 @@ pub struct WinScreen;
 #[instrument(level = "debug", skip_all)]
 pub fn win_screen(
     fonts: Res<FontAssets>,
+    locale: Locale,
     players: Res<Players>,
+    settings: Res<Settings>,
+    config: Res<GameConfig>,
+    series: Res<Series>,
+    rating_changes: Res<RatingChanges>,
+    queue: Res<AnimationQueue>,
     mut commands: Commands,
-    mut event: EventReader<WinEvent>,
+    mut pending: Local<Option<WinEvent>>,
+    mut reset: EventReader<ResetGameEvent>,
+    mut wins: EventReader<WinEvent>,
+    mut sounds: EventWriter<PlaySound>,
     previous_win_screens: Query<(Entity,), With<WinScreen>>,
 ) {
-    let event = event.iter().next().unwrap();
+    if reset.iter().count() > 0 {
+        *pending = None;
+    }
+    if let Some(win) = wins.iter().next() {
+        *pending = Some(WinEvent {
+            winner: win.winner,
+            timed_out: win.timed_out,
+        });
+    }
+    // let the last move play out first
+    if !queue.is_idle() {
+        return;
+    }
+    let Some(event) = pending.take() else {
+        return;
+    };
+    sounds.send(
+        match event.winner {
+            Some(_) => Cue::Win,
+            None => Cue::Tie,
+        }
+        .into(),
+    );
 
     previous_win_screens.iter().for_each(|(entity,)| {
         commands.entity(entity).despawn_recursive();
@@ -127,17 +161,27 @@ pub fn win_screen(
                 Some(id) => {
                     let player = players.by_id(id).expect("valid player id");
                     parent.spawn((TextBundle::from_section(
-                        format!("{} won!", player.name),
+                        locale.t_args("win-winner", &[("player", player.name.clone().into())]),
                         TextStyle {
                             font: fonts.fira_sans.clone(),
                             font_size: 48.0,
                             color: Color::BLACK,
                         },
                     ),));
+                    if let Some(loser) = event.timed_out.and_then(|id| players.by_id(id)) {
+                        parent.spawn((TextBundle::from_section(
+                            locale.t_args("win-timeout", &[("player", loser.name.clone().into())]),
+                            TextStyle {
+                                font: fonts.fira_sans.clone(),
+                                font_size: 24.0,
+                                color: Color::BLACK,
+                            },
+                        ),));
+                    }
                 }
                 None => {
                     parent.spawn((TextBundle::from_section(
-                        "Tie!",
+                        locale.t("win-tie"),
                         TextStyle {
                             font: fonts.fira_sans.clone(),
                             font_size: 48.0,
@@ -145,7 +189,7 @@ pub fn win_screen(
                         },
                     ),));
                     parent.spawn((TextBundle::from_section(
-                        "No more cats!",
+                        locale.t("win-no-more-cats"),
                         TextStyle {
                             font: fonts.fira_sans.clone(),
                             font_size: 24.0,
@@ -154,6 +198,10 @@ pub fn win_screen(
                     ),));
                 }
             };
+            if config.series != SeriesLength::Single {
+                scoreboard(parent, &fonts, &locale, &players, &series, config.series);
+            }
+            ratings(parent, &fonts, &locale, &players, &rating_changes);
             parent
                 .spawn((
                     ButtonBundle {
@@ -171,7 +219,10 @@ pub fn win_screen(
                 ))
                 .with_children(|button| {
                     let mut text = TextBundle::from_section(
-                        "Press R to restart",
+                        locale.t_args(
+                            "win-restart-hint",
+                            &[("key", format!("{:?}", settings.keys.restart).into())],
+                        ),
                         TextStyle {
                             font: fonts.fira_sans.clone(),
                             font_size: 18.0,
@@ -184,6 +235,100 @@ pub fn win_screen(
         });
 }
 
+/// Wins of each player so far, and who won the match or starts the next game
+fn scoreboard(
+    parent: &mut ChildBuilder,
+    fonts: &FontAssets,
+    locale: &Locale,
+    players: &Players,
+    series: &Series,
+    length: SeriesLength,
+) {
+    let style = TextStyle {
+        font: fonts.fira_sans.clone(),
+        font_size: 24.0,
+        color: Color::BLACK,
+    };
+    let name = |idx: usize| {
+        players
+            .by_id(PlayerId::new(idx as u8))
+            .map_or_else(String::new, |player| player.name.clone())
+    };
+
+    let mut score = series
+        .wins
+        .iter()
+        .enumerate()
+        .map(|(idx, wins)| {
+            locale.t_args(
+                "series-score",
+                &[("player", name(idx).into()), ("wins", (*wins).into())],
+            )
+        })
+        .collect::<Vec<_>>();
+    if series.ties > 0 {
+        score.push(locale.t_args("series-ties", &[("count", series.ties.into())]));
+    }
+    parent.spawn((TextBundle::from_section(score.join(", "), style.clone()),));
+
+    let status = if series.over {
+        match series.leader() {
+            Some(winner) => locale.t_args(
+                "series-winner",
+                &[("player", name(winner.0 as usize).into())],
+            ),
+            None => locale.t("series-tied"),
+        }
+    } else {
+        locale.t_args(
+            "series-next",
+            &[
+                ("game", (series.games_played() + 1).into()),
+                ("games", length.games().into()),
+                ("player", name(series.first_player).into()),
+            ],
+        )
+    };
+    parent.spawn((TextBundle::from_section(status, style),));
+}
+
+/// New rating of each player and how much it changed with this game
+fn ratings(
+    parent: &mut ChildBuilder,
+    fonts: &FontAssets,
+    locale: &Locale,
+    players: &Players,
+    changes: &RatingChanges,
+) {
+    let lines = changes
+        .0
+        .iter()
+        .filter_map(|change| {
+            let player = players.by_id(change.player)?;
+            let difference = change.after.round() - change.before.round();
+            Some(locale.t_args(
+                "win-rating",
+                &[
+                    ("player", player.name.clone().into()),
+                    ("rating", (change.after.round() as i32).into()),
+                    ("change", format!("{:+}", difference as i32).into()),
+                ],
+            ))
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return;
+    }
+    parent.spawn((TextBundle::from_section(
+        lines.join("\n"),
+        TextStyle {
+            font: fonts.fira_sans.clone(),
+            font_size: 18.0,
+            color: Color::DARK_GRAY,
+        },
+    ),));
+}
+
 impl From<ListenedEvent<Click>> for ResetGameEvent {
     fn from(_event: ListenedEvent<Click>) -> Self {
         ResetGameEvent
@@ -195,3 +340,67 @@ pub fn win_screen_cleanup(mut commands: Commands, query: Query<Entity, With<WinS
         commands.entity(entity).despawn_recursive();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use hexx::Hex;
+
+    use crate::{
+        cats::{animation::AnimationQueue, Cat, Meowple},
+        config::{GameConfig, Rules},
+        events::{CatsGraduated, ResetGameEvent, WinEvent},
+        grid::{GridCell, Map},
+        players::{PlayerId, Players},
+        GameState,
+    };
+
+    use super::{super::graduation::graduate, win_condition};
+
+    #[test]
+    fn graduating_cats_do_not_win() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin::default())
+            .add_asset::<StandardMaterial>()
+            .add_state::<GameState>()
+            .add_event::<ResetGameEvent>()
+            .add_event::<WinEvent>()
+            .add_event::<CatsGraduated>()
+            .insert_resource(GameConfig {
+                rules: Rules::Graduation,
+                ..default()
+            })
+            .init_resource::<Players>()
+            .init_resource::<AnimationQueue>()
+            .init_resource::<Map>()
+            .add_systems((graduate, win_condition).chain());
+
+        // The adult just placed at 0 completes a row of adults, but the
+        // kitten at 1 makes -1, 0 and 1 graduate first
+        let pieces = [
+            (-2, Cat::Adult),
+            (-1, Cat::Adult),
+            (0, Cat::Adult),
+            (1, Cat::Kitten),
+        ];
+        for (x, cat) in pieces {
+            let hex = Hex::new(x, 0);
+            let entity = app
+                .world
+                .spawn((
+                    cat,
+                    PlayerId::new(0),
+                    Meowple,
+                    GridCell(hex),
+                    Handle::<StandardMaterial>::default(),
+                ))
+                .id();
+            app.world.resource_mut::<Map>().add_cat(hex, entity);
+        }
+        app.update();
+
+        assert_eq!(app.world.resource::<Map>().cats.len(), 1);
+        assert!(app.world.resource::<Events<WinEvent>>().is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+use bevy::prelude::*;
+use hexx::{Hex, HexLayout};
+use tracing::instrument;
+
+use crate::{
+    events::GridCellClicked,
+    settings::{KeyBindings, Settings},
+};
+
+use super::{Hovered, Map, MapSettings, Platform};
+
+/// How far the gamepad stick has to be pushed to move the cursor
+const STICK_THRESHOLD: f32 = 0.5;
+/// Seconds between cursor steps while the gamepad stick is held
+const STICK_REPEAT: f32 = 0.2;
+
+/// Cell selected with keyboard or gamepad
+#[derive(Debug, Default, Resource)]
+pub struct GridCursor {
+    pub hex: Option<Hex>,
+    /// Vertical moves alternate between the two cells above/below, so holding
+    /// "up" walks in a straight line
+    lean_left: bool,
+}
+
+/// Marks the platform the cursor is on
+#[derive(Debug, Clone, Copy, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct CursorCell;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CursorAction {
+    /// Move in a screen direction (x to the right, y up)
+    Move(Vec2),
+    Confirm,
+    Cancel,
+}
+
+fn move_keys(keys: &KeyBindings) -> [(KeyCode, Vec2); 8] {
+    [
+        (keys.cursor_up, Vec2::Y),
+        (keys.cursor_up_alt, Vec2::Y),
+        (keys.cursor_down, Vec2::NEG_Y),
+        (keys.cursor_down_alt, Vec2::NEG_Y),
+        (keys.cursor_left, Vec2::NEG_X),
+        (keys.cursor_left_alt, Vec2::NEG_X),
+        (keys.cursor_right, Vec2::X),
+        (keys.cursor_right_alt, Vec2::X),
+    ]
+}
+
+pub fn keyboard_input(
+    keys: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    mut actions: EventWriter<CursorAction>,
+) {
+    let move_keys = move_keys(&settings.keys);
+    // Holding one direction while tapping another moves diagonally
+    if move_keys.iter().any(|(key, _)| keys.just_pressed(*key)) {
+        let direction = move_keys
+            .iter()
+            .filter(|(key, _)| keys.pressed(*key))
+            .map(|(_, direction)| *direction)
+            .sum::<Vec2>();
+        if direction != Vec2::ZERO {
+            actions.send(CursorAction::Move(direction));
+        }
+    }
+
+    if keys.just_pressed(settings.keys.confirm) {
+        actions.send(CursorAction::Confirm);
+    }
+    if keys.just_pressed(settings.keys.cancel) {
+        actions.send(CursorAction::Cancel);
+    }
+}
+
+pub fn gamepad_input(
+    time: Res<Time>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    buttons: Res<Input<GamepadButton>>,
+    mut cooldown: Local<f32>,
+    mut actions: EventWriter<CursorAction>,
+) {
+    *cooldown -= time.delta_seconds();
+
+    for gamepad in gamepads.iter() {
+        let button = |button_type| GamepadButton::new(gamepad, button_type);
+        let axis = |axis_type| {
+            axes.get(GamepadAxis::new(gamepad, axis_type))
+                .unwrap_or_default()
+        };
+
+        let stick = Vec2::new(
+            axis(GamepadAxisType::LeftStickX),
+            axis(GamepadAxisType::LeftStickY),
+        );
+        if stick.length() > STICK_THRESHOLD {
+            if *cooldown <= 0. {
+                *cooldown = STICK_REPEAT;
+                actions.send(CursorAction::Move(stick));
+            }
+        } else {
+            *cooldown = 0.;
+        }
+
+        let dpad = [
+            (GamepadButtonType::DPadUp, Vec2::Y),
+            (GamepadButtonType::DPadDown, Vec2::NEG_Y),
+            (GamepadButtonType::DPadLeft, Vec2::NEG_X),
+            (GamepadButtonType::DPadRight, Vec2::X),
+        ];
+        for (button_type, direction) in dpad {
+            if buttons.just_pressed(button(button_type)) {
+                actions.send(CursorAction::Move(direction));
+            }
+        }
+
+        if buttons.just_pressed(button(GamepadButtonType::South)) {
+            actions.send(CursorAction::Confirm);
+        }
+        if buttons.just_pressed(button(GamepadButtonType::East)) {
+            actions.send(CursorAction::Cancel);
+        }
+    }
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn move_cursor(
+    settings: Res<MapSettings>,
+    map: Res<Map>,
+    mut cursor: ResMut<GridCursor>,
+    mut actions: EventReader<CursorAction>,
+    mut clicks: EventWriter<GridCellClicked>,
+    cameras: Query<&Transform, With<Camera3d>>,
+) {
+    let layout = HexLayout {
+        hex_size: settings.cell_size,
+        ..default()
+    };
+    let camera = cameras.get_single().copied().unwrap_or_default();
+
+    for action in actions.iter() {
+        match *action {
+            CursorAction::Move(direction) => {
+                let Some(hex) = cursor.hex.filter(|hex| map.cell_by_hex(*hex).is_some()) else {
+                    // first input only shows the cursor
+                    cursor.hex = Some(Hex::ZERO);
+                    continue;
+                };
+                let next =
+                    neighbor_towards(hex, direction, &camera, &layout, &mut cursor.lean_left);
+                if map.cell_by_hex(next).is_some() {
+                    cursor.hex = Some(next);
+                }
+            }
+            CursorAction::Confirm => {
+                let Some(cell) = cursor.hex.and_then(|hex| map.cell_by_hex(hex)) else {
+                    continue;
+                };
+                clicks.send(GridCellClicked {
+                    cell,
+                    touch: false,
+                    primary: true,
+                });
+            }
+            CursorAction::Cancel => cursor.hex = None,
+        }
+    }
+}
+
+/// Neighbor of `hex` that is closest to the screen `direction`
+fn neighbor_towards(
+    hex: Hex,
+    direction: Vec2,
+    camera: &Transform,
+    layout: &HexLayout,
+    lean_left: &mut bool,
+) -> Hex {
+    let flatten = |v: Vec3| Vec2::new(v.x, v.z).normalize_or_zero();
+    let right = flatten(camera.right());
+    // Tilted cameras look along the ground, top-down cameras have it as up
+    let up = flatten(camera.up());
+
+    let mut target = right * direction.x + up * direction.y;
+    if direction.x == 0. {
+        // break the tie between the two cells above/below on pointy grids
+        target += right * if *lean_left { -0.1 } else { 0.1 };
+        *lean_left = !*lean_left;
+    }
+
+    let origin = layout.hex_to_world_pos(hex);
+    Hex::NEIGHBORS_COORDS
+        .into_iter()
+        .map(|offset| hex + offset)
+        .max_by(|a, b| {
+            let score = |neighbor: &Hex| {
+                (layout.hex_to_world_pos(*neighbor) - origin)
+                    .normalize_or_zero()
+                    .dot(target)
+            };
+            score(a).total_cmp(&score(b))
+        })
+        .unwrap_or(hex)
+}
+
+/// Put [`Hovered`] and [`CursorCell`] on the platform under the cursor
+pub fn show_cursor(
+    mut commands: Commands,
+    map: Res<Map>,
+    cursor: Res<GridCursor>,
+    cells: Query<(Entity, Option<&Hovered>, Option<&CursorCell>), With<Platform>>,
+) {
+    let cursor_cell = cursor.hex.and_then(|hex| map.cell_by_hex(hex));
+    for (entity, hovered, marker) in cells.iter() {
+        if Some(entity) == cursor_cell {
+            if hovered.is_none() || marker.is_none() {
+                commands.entity(entity).insert((Hovered, CursorCell));
+            }
+        } else if marker.is_some() {
+            commands.entity(entity).remove::<(Hovered, CursorCell)>();
+        }
+    }
+}
+
+/// Using the mouse hides the keyboard cursor
+pub fn hide_cursor_on_mouse_move(
+    touches: Res<Touches>,
+    mut cursor: ResMut<GridCursor>,
+    mut mouse: EventReader<CursorMoved>,
+) {
+    // browsers move the mouse cursor along with taps, which select cells
+    let touched = touches.iter().next().is_some() || touches.any_just_released();
+    if mouse.iter().count() > 0 && !touched && cursor.hex.is_some() {
+        cursor.hex = None;
+    }
+}
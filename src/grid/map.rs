@@ -61,3 +61,11 @@ impl Default for MapSettings {
         }
     }
 }
+
+impl MapSettings {
+    /// World space radius of the board
+    pub fn board_radius(&self) -> f32 {
+        // cells are spaced about sqrt(3) cell sizes apart
+        (self.map_radius as f32 + 0.5) * self.cell_size.max_element() * 3f32.sqrt()
+    }
+}
@@ -1,13 +1,18 @@
 use bevy::prelude::*;
 use hexx::Hex;
 
-use crate::{events::ResetGameEvent, players::Players, GameState};
+use crate::{events::ResetGameEvent, menu::key_bindings_active, players::Players, GameState};
 
-use self::setup::{setup_grid, CellMaterials};
+use self::{
+    cursor::{CursorAction, CursorCell},
+    setup::{setup_grid, CellMaterials},
+};
 
+mod cursor;
 mod map;
 mod setup;
 
+pub use cursor::GridCursor;
 pub use map::{Map, MapSettings};
 
 pub struct HexGridPlugin;
@@ -18,20 +23,32 @@ impl Plugin for HexGridPlugin {
         app.register_type::<MapSettings>();
         app.register_type::<GridCell>();
         app.register_type::<Grid>();
+        app.register_type::<CursorCell>();
 
         app.init_resource::<MapSettings>();
         app.init_resource::<Map>();
+        app.init_resource::<GridCursor>();
+        app.add_event::<CursorAction>();
 
         app.add_system(setup_grid.in_schedule(OnExit(GameState::Loading)));
-        app.add_system(reset_map.run_if(on_event::<ResetGameEvent>()));
+        app.add_system(setup_grid.run_if(on_event::<ResetGameEvent>()));
         app.add_system(highlight_cell.in_set(OnUpdate(GameState::Playing)));
+        app.add_systems(
+            (
+                cursor::hide_cursor_on_mouse_move,
+                cursor::keyboard_input.run_if(key_bindings_active),
+                cursor::gamepad_input,
+                cursor::move_cursor,
+                cursor::show_cursor,
+            )
+                .chain()
+                .before(highlight_cell)
+                .in_set(OnUpdate(GameState::Playing)),
+        );
+        app.add_system(update_materials.run_if(resource_exists_and_changed::<Players>()));
     }
 }
 
-fn reset_map(mut map: ResMut<Map>) {
-    map.cats = default();
-}
-
 #[derive(Debug, Clone, Copy, Default, Component, Reflect)]
 #[reflect(Component)]
 pub struct Grid;
@@ -56,22 +73,54 @@ impl std::ops::Deref for GridCell {
 #[reflect(Component)]
 pub struct Hovered;
 
+/// Keep hover colors in sync with the player colors
+fn update_materials(
+    players: Res<Players>,
+    cell_materials: Option<Res<CellMaterials>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let Some(cell_materials) = cell_materials else {
+        return;
+    };
+    for (handle, player) in cell_materials
+        .hovered_by_player
+        .iter()
+        .zip(&players.players)
+    {
+        if materials
+            .get(handle)
+            .map_or(false, |m| m.base_color != player.color)
+        {
+            if let Some(material) = materials.get_mut(handle) {
+                material.base_color = player.color;
+            }
+        }
+    }
+}
+
 fn highlight_cell(
     players: Res<Players>,
     materials: Res<CellMaterials>,
     map: Res<Map>,
     mut hovered_cell: Query<
-        (&mut Handle<StandardMaterial>, &GridCell),
+        (
+            &mut Handle<StandardMaterial>,
+            &GridCell,
+            Option<&CursorCell>,
+        ),
         (With<Platform>, With<Hovered>),
     >,
     mut other_cells: Query<(&mut Handle<StandardMaterial>,), (With<Platform>, Without<Hovered>)>,
 ) {
     let player_material = materials.hovered_by_player[players.current().id.0 as usize].clone();
-    hovered_cell.iter_mut().for_each(|(mut material, cell)| {
-        if map.cat_by_hex(cell.0).is_none() {
-            *material = player_material.clone();
-        }
-    });
+    hovered_cell
+        .iter_mut()
+        .for_each(|(mut material, cell, cursor)| {
+            // the keyboard cursor also needs to be visible on occupied cells
+            if map.cat_by_hex(cell.0).is_none() || cursor.is_some() {
+                *material = player_material.clone();
+            }
+        });
 
     other_cells.iter_mut().for_each(|(mut material,)| {
         *material = materials.default.clone();
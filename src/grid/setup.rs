@@ -8,7 +8,10 @@ use bevy_mod_picking::{
 };
 use hexx::{shapes, Hex, HexLayout};
 
-use crate::events::GridCellClicked;
+use crate::{
+    config::GameConfig,
+    events::{BoardReady, GridCellClicked},
+};
 
 use super::{Grid, GridCell, Hovered, Map, MapSettings, Platform};
 
@@ -16,16 +19,25 @@ use super::{Grid, GridCell, Hovered, Map, MapSettings, Platform};
 #[reflect(Resource)]
 pub struct CellMaterials {
     pub default: Handle<StandardMaterial>,
-    pub hovered_by_player: [Handle<StandardMaterial>; 2],
+    pub hovered_by_player: Vec<Handle<StandardMaterial>>,
 }
 
+/// Spawn the grid platforms, replacing any previous grid
 pub fn setup_grid(
     mut commands: Commands,
     settings: Res<MapSettings>,
+    config: Res<GameConfig>,
     mut map: ResMut<Map>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    previous_grids: Query<Entity, With<Grid>>,
+    mut ready: EventWriter<BoardReady>,
 ) {
+    for grid in previous_grids.iter() {
+        commands.entity(grid).despawn_recursive();
+    }
+    map.cats = default();
+
     let MapSettings {
         cell_size: hex_size,
         map_radius,
@@ -42,10 +54,11 @@ pub fn setup_grid(
 
     commands.insert_resource(CellMaterials {
         default: default_material.clone(),
-        hovered_by_player: [
-            materials.add(Color::LIME_GREEN.into()),
-            materials.add(Color::ORANGE.into()),
-        ],
+        hovered_by_player: config
+            .colors()
+            .into_iter()
+            .map(|color| materials.add(color.into()))
+            .collect(),
     });
 
     let parent = commands
@@ -83,6 +96,7 @@ pub fn setup_grid(
         .push_children(&entities.values().copied().collect::<Vec<_>>());
 
     map.cells = entities;
+    ready.send(BoardReady);
 }
 
 fn circle_column(hex_layout: &HexLayout, settings: &MapSettings) -> Mesh {
@@ -0,0 +1,406 @@
+//! Every move of the current game, with the position after it, so earlier
+//! positions can be looked at without changing the game.
+
+use bevy::{prelude::*, ui::FocusPolicy, utils::HashMap};
+use hexx::Hex;
+use tracing::instrument;
+
+use crate::{
+    accessibility::describe_hex,
+    cats::{animation::AnimationQueue, Cat, Meowple},
+    events::{NewCat, ResetGameEvent},
+    grid::Map,
+    loading::FontAssets,
+    locale::Locale,
+    menu::key_bindings_active,
+    players::{PlayerId, Players},
+    rules::{self, Boop},
+    settings::Settings,
+    GameState,
+};
+
+mod view;
+
+/// How many moves are listed at once
+const HISTORY_LINES: usize = 12;
+
+pub struct HistoryPlugin;
+
+impl Plugin for HistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MoveHistory>();
+        app.register_type::<MoveHistory>();
+        app.register_type::<view::HistoryPiece>();
+        app.register_type::<view::LastMoveRing>();
+
+        app.add_system(record_moves.in_base_set(CoreSet::PostUpdate));
+        app.add_systems(
+            (toggle_panel.run_if(key_bindings_active), select_entry).distributive_run_if(in_game),
+        );
+        app.add_systems(
+            (show_panel, view::show_position, view::show_last_move)
+                .distributive_run_if(not_loading),
+        );
+        app.add_system(clear_history.run_if(on_event::<ResetGameEvent>()));
+    }
+}
+
+fn not_loading(state: Res<State<GameState>>) -> bool {
+    state.0 != GameState::Loading
+}
+
+/// Playing, or looking at the board after the game is over
+fn in_game(state: Res<State<GameState>>) -> bool {
+    matches!(state.0, GameState::Playing | GameState::GameOver)
+}
+
+/// Pieces on the board, sorted by cell
+pub type Position = Vec<(Hex, Cat, PlayerId)>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveRecord {
+    pub player: PlayerId,
+    pub cat: Cat,
+    pub hex: Hex,
+    pub boops: Vec<Boop>,
+    /// Board once the move and everything it caused is done
+    pub position: Position,
+}
+
+/// Moves of the current game, oldest first
+#[derive(Debug, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct MoveHistory {
+    #[reflect(ignore)]
+    moves: Vec<MoveRecord>,
+    /// Pieces on the board before the first move, when starting from a
+    /// prepared position
+    #[reflect(ignore)]
+    start: Position,
+    /// Move whose position is shown instead of the live game
+    pub viewing: Option<usize>,
+    /// Whether the list of moves is shown on screen
+    pub visible: bool,
+}
+
+impl Default for MoveHistory {
+    fn default() -> Self {
+        Self {
+            moves: Vec::new(),
+            start: Vec::new(),
+            viewing: None,
+            visible: true,
+        }
+    }
+}
+
+impl MoveHistory {
+    pub fn moves(&self) -> &[MoveRecord] {
+        &self.moves
+    }
+
+    /// Move shown on the board, the latest one unless an earlier one is
+    /// being viewed
+    pub fn shown(&self) -> Option<(usize, &MoveRecord)> {
+        let idx = self.viewing.or(self.moves.len().checked_sub(1))?;
+        Some((idx, self.moves.get(idx)?))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+enum HistoryButton {
+    Move(usize),
+    Live,
+}
+
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+struct HistoryPanel;
+
+/// Pieces on the board as they are now
+pub fn position(map: &Map, piece: impl Fn(Entity) -> Option<(Cat, PlayerId)>) -> Position {
+    let mut position = map
+        .cats
+        .iter()
+        .filter_map(|(hex, entity)| {
+            let (cat, player) = piece((*entity)?)?;
+            Some((*hex, cat, player))
+        })
+        .collect::<Vec<_>>();
+    position.sort_by_key(|(hex, ..)| (hex.x, hex.y));
+    position
+}
+
+/// Add new moves, and keep the latest one's position up to date until the
+/// next move, since graduations happen a frame later
+#[instrument(level = "debug", skip_all)]
+fn record_moves(
+    map: Res<Map>,
+    mut history: ResMut<MoveHistory>,
+    mut new_cats: EventReader<NewCat>,
+    cats: Query<(&Cat, &PlayerId), With<Meowple>>,
+    added: Query<(), Added<Meowple>>,
+) {
+    let mut recorded = false;
+    for NewCat {
+        player,
+        cat,
+        position,
+        ..
+    } in new_cats.iter()
+    {
+        let before = history
+            .moves
+            .last()
+            .map_or(&history.start, |last| &last.position)
+            .iter()
+            .map(|(hex, cat, _)| (*hex, *cat))
+            .collect::<HashMap<_, _>>();
+        let boops = rules::boops(
+            *position,
+            *cat,
+            |hex| map.cell_by_hex(hex).is_some(),
+            |hex| before.get(&hex).copied(),
+        );
+        debug!(?player, ?position, boops = boops.len(), "Recording move");
+        history.moves.push(MoveRecord {
+            player: *player,
+            cat: *cat,
+            hex: *position,
+            boops,
+            position: Vec::new(),
+        });
+        recorded = true;
+    }
+
+    // pieces are only found once they are spawned, after the map changed
+    if !(recorded || map.is_changed() || !added.is_empty()) {
+        return;
+    }
+    let now = position(&map, |entity| {
+        cats.get(entity).ok().map(|(cat, player)| (*cat, *player))
+    });
+    if history.moves.is_empty() {
+        history.start = now;
+        return;
+    }
+    if history
+        .moves
+        .last()
+        .map_or(true, |last| last.position == now)
+    {
+        return;
+    }
+    if let Some(last) = history.moves.last_mut() {
+        last.position = now;
+    }
+}
+
+fn clear_history(mut history: ResMut<MoveHistory>) {
+    let visible = history.visible;
+    *history = MoveHistory {
+        visible,
+        ..default()
+    };
+}
+
+fn toggle_panel(
+    keys: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    mut history: ResMut<MoveHistory>,
+) {
+    if keys.just_pressed(settings.keys.history) {
+        history.visible = !history.visible;
+    }
+}
+
+/// Clicking a move shows the position after it, until going back to the
+/// live game
+#[instrument(level = "debug", skip_all)]
+fn select_entry(
+    queue: Res<AnimationQueue>,
+    mut history: ResMut<MoveHistory>,
+    buttons: Query<(&Interaction, &HistoryButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        let viewing = match button {
+            HistoryButton::Move(idx) if idx + 1 < history.moves.len() => Some(*idx),
+            HistoryButton::Move(_) | HistoryButton::Live => None,
+        };
+        // the live cats are hidden while viewing, which would cut their
+        // animations short
+        if viewing.is_some() && !queue.is_idle() {
+            debug!("Not viewing earlier move while animations play");
+            continue;
+        }
+        if history.viewing != viewing {
+            info!(?viewing, "Viewing move");
+            history.viewing = viewing;
+        }
+    }
+}
+
+/// "3. Green kitten (1, -2): (1, -1) → (1, 0), (2, -2) off"
+fn notation(locale: &Locale, players: &Players, idx: usize, record: &MoveRecord) -> String {
+    let player = players
+        .by_id(record.player)
+        .map_or_else(|| record.player.to_string(), |player| player.name.clone());
+    let piece = match record.cat {
+        Cat::Kitten => "kitten",
+        Cat::Adult => "cat",
+    };
+    let mut text = locale.t_args(
+        "history-move",
+        &[
+            ("number", (idx + 1).into()),
+            ("player", player.into()),
+            ("piece", piece.into()),
+            ("cell", describe_hex(record.hex).into()),
+        ],
+    );
+    let boops = record
+        .boops
+        .iter()
+        .map(|Boop { from, to }| match to {
+            Some(to) => locale.t_args(
+                "history-boop-to",
+                &[
+                    ("from", describe_hex(*from).into()),
+                    ("to", describe_hex(*to).into()),
+                ],
+            ),
+            None => locale.t_args("history-boop-off", &[("from", describe_hex(*from).into())]),
+        })
+        .collect::<Vec<_>>();
+    if !boops.is_empty() {
+        text.push_str(": ");
+        text.push_str(&boops.join(", "));
+    }
+    text
+}
+
+/// List of moves in the top right corner, around the one shown on the board
+fn show_panel(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Locale,
+    history: Res<MoveHistory>,
+    players: Res<Players>,
+    settings: Res<Settings>,
+    state: Res<State<GameState>>,
+    previous: Query<Entity, With<HistoryPanel>>,
+) {
+    if !(history.is_changed() || settings.is_changed() || state.is_changed()) {
+        return;
+    }
+    for entity in previous.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let playing = matches!(state.0, GameState::Playing | GameState::GameOver);
+    if !playing || !history.visible || history.moves.is_empty() {
+        return;
+    }
+
+    let text_style = |color: Color| TextStyle {
+        font: fonts.fira_sans.clone(),
+        font_size: 16.0,
+        color,
+    };
+    let shown = history.shown().map_or(0, |(idx, _)| idx);
+    let end = (shown + HISTORY_LINES / 2 + 1)
+        .max(HISTORY_LINES)
+        .min(history.moves.len());
+    let start = end.saturating_sub(HISTORY_LINES);
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        right: Val::Px(10.),
+                        top: Val::Px(10.),
+                        ..default()
+                    },
+                    max_size: Size::width(Val::Px(400.)),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.)),
+                    gap: Size::height(Val::Px(2.)),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::WHITE.with_a(0.7)),
+                ..default()
+            },
+            Name::from("History"),
+            HistoryPanel,
+        ))
+        .with_children(|panel| {
+            panel.spawn(TextBundle::from_section(
+                locale.t("history-title"),
+                text_style(Color::BLACK),
+            ));
+            for idx in start..end {
+                let record = &history.moves[idx];
+                let color = players
+                    .by_id(record.player)
+                    .map_or(Color::WHITE, |player| player.color);
+                let background = if idx == shown {
+                    color.with_a(0.6)
+                } else {
+                    Color::NONE
+                };
+                panel
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                padding: UiRect::horizontal(Val::Px(4.)),
+                                ..default()
+                            },
+                            background_color: BackgroundColor(background),
+                            ..default()
+                        },
+                        HistoryButton::Move(idx),
+                    ))
+                    .with_children(|button| {
+                        button.spawn(button_text(
+                            notation(&locale, &players, idx, record),
+                            text_style(Color::BLACK),
+                        ));
+                    });
+            }
+            if let Some(viewing) = history.viewing {
+                panel.spawn(TextBundle::from_section(
+                    locale.t_args("history-viewing", &[("number", (viewing + 1).into())]),
+                    text_style(Color::DARK_GRAY),
+                ));
+                panel
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                padding: UiRect::all(Val::Px(5.)),
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            background_color: BackgroundColor(Color::WHITE.with_a(0.8)),
+                            ..default()
+                        },
+                        HistoryButton::Live,
+                    ))
+                    .with_children(|button| {
+                        button.spawn(button_text(
+                            locale.t("history-live"),
+                            text_style(Color::DARK_GRAY),
+                        ));
+                    });
+            }
+        });
+}
+
+fn button_text(text: String, style: TextStyle) -> TextBundle {
+    let mut text = TextBundle::from_section(text, style);
+    text.focus_policy = FocusPolicy::Pass;
+    text
+}
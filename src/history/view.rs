@@ -0,0 +1,148 @@
+use bevy::{
+    gltf::{Gltf, GltfMesh},
+    prelude::{shape::Torus, *},
+};
+use hexx::{Hex, HexLayout};
+use tracing::instrument;
+
+use crate::{
+    cats::{cat_mesh, Cat, Meowple},
+    config::adult_color,
+    grid::{GridCell, Map, MapSettings},
+    loading::CatModel,
+    players::Players,
+};
+
+use super::MoveHistory;
+
+/// Stand-in for a cat of an earlier position
+#[derive(Debug, Clone, Copy, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct HistoryPiece;
+
+/// Outline around the cell of the last move
+#[derive(Debug, Clone, Copy, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct LastMoveRing;
+
+/// Replace the live cats with the position of the viewed move, and bring
+/// them back afterwards
+#[instrument(level = "debug", skip_all)]
+pub fn show_position(
+    mut commands: Commands,
+    history: Res<MoveHistory>,
+    settings: Res<MapSettings>,
+    players: Res<Players>,
+    cat_model: Res<CatModel>,
+    assets_gltf: Res<Assets<Gltf>>,
+    assets_gltfmesh: Res<Assets<GltfMesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut shown: Local<Option<usize>>,
+    mut live_cats: Query<&mut Visibility, (With<Meowple>, With<GridCell>)>,
+    pieces: Query<Entity, With<HistoryPiece>>,
+) {
+    if *shown == history.viewing {
+        return;
+    }
+    *shown = history.viewing;
+    for piece in pieces.iter() {
+        commands.entity(piece).despawn_recursive();
+    }
+    let visibility = if history.viewing.is_some() {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+    for mut cat in live_cats.iter_mut() {
+        *cat = visibility;
+    }
+
+    let Some(record) = history.viewing.and_then(|idx| history.moves().get(idx)) else {
+        return;
+    };
+    let Some(gltf) = assets_gltf.get(&cat_model.mesh) else {
+        return;
+    };
+    debug!(pieces = record.position.len(), "Showing earlier position");
+
+    let layout = HexLayout {
+        hex_size: settings.cell_size,
+        ..default()
+    };
+    for (hex, cat, owner) in &record.position {
+        let color = players.by_id(*owner).map_or(Color::WHITE, |p| p.color);
+        let color = match cat {
+            Cat::Kitten => color,
+            Cat::Adult => adult_color(color),
+        };
+        let pos = layout.hex_to_world_pos(*hex);
+        // cats sit on top of the cells, see `spawn_cats`
+        let translation = Vec3::new(pos.x, settings.column_height / 2., pos.y);
+        commands.spawn((
+            PbrBundle {
+                mesh: cat_mesh(*cat, gltf, &assets_gltfmesh),
+                material: materials.add(StandardMaterial {
+                    base_color: color,
+                    ..default()
+                }),
+                transform: Transform::from_translation(translation).with_scale(Vec3::splat(2.)),
+                ..default()
+            },
+            Name::from("History piece"),
+            HistoryPiece,
+        ));
+    }
+}
+
+/// Ring around the cell where the shown move was made
+#[instrument(level = "trace", skip_all)]
+pub fn show_last_move(
+    mut commands: Commands,
+    history: Res<MoveHistory>,
+    settings: Res<MapSettings>,
+    map: Res<Map>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut shown: Local<Option<Hex>>,
+    rings: Query<Entity, With<LastMoveRing>>,
+) {
+    let hex = history.shown().map(|(_, record)| record.hex);
+    if *shown == hex && !map.is_changed() {
+        return;
+    }
+    *shown = hex;
+    for ring in rings.iter() {
+        commands.entity(ring).despawn_recursive();
+    }
+    let Some(cell) = hex.and_then(|hex| map.cell_by_hex(hex)) else {
+        return;
+    };
+
+    // inside the analysis overlay's rings, so all of them fit
+    let mesh = meshes.add(
+        Torus {
+            radius: settings.cell_size.x * 0.9,
+            ring_radius: 0.1,
+            ..default()
+        }
+        .into(),
+    );
+    let material = materials.add(StandardMaterial {
+        base_color: Color::WHITE,
+        unlit: true,
+        ..default()
+    });
+    commands.entity(cell).with_children(|parent| {
+        parent.spawn((
+            PbrBundle {
+                mesh,
+                material,
+                // on top of the platform
+                transform: Transform::from_xyz(0., settings.column_height / 2., 0.),
+                ..default()
+            },
+            Name::from("Last move"),
+            LastMoveRing,
+        ));
+    });
+}
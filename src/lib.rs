@@ -4,18 +4,36 @@ use bevy::prelude::*;
 use bevy_mod_picking::prelude::*;
 use bevy_tweening::TweeningPlugin;
 
+mod accessibility;
+mod camera;
 mod cats;
+mod config;
 mod events;
 mod gameplay;
 mod grid;
+mod history;
 mod loading;
+mod locale;
+mod menu;
+mod modes;
+mod music;
 mod players;
+mod profiles;
+pub mod puzzles;
+pub mod rules;
+mod search;
+mod settings;
+mod sound;
+mod storage;
 
 #[derive(States, Default, Clone, Eq, PartialEq, Debug, Hash)]
 pub enum GameState {
     #[default]
     Loading,
+    /// Main menu where the next game is set up
+    Menu,
     Playing,
+    Paused,
     GameOver,
 }
 
@@ -26,18 +44,28 @@ impl Plugin for GamePlugin {
         app.add_state::<GameState>();
 
         app.add_plugin(loading::LoadingPlugin);
+        app.add_plugin(locale::LocalePlugin);
         app.add_plugins(DefaultPickingPlugins);
         app.add_plugin(TweeningPlugin);
 
         app.add_plugin(events::EventsPlugin);
+        app.add_plugin(camera::CameraPlugin);
+        app.add_plugin(settings::SettingsPlugin);
+        app.add_plugin(sound::SoundPlugin);
+        app.add_plugin(music::MusicPlugin);
+        app.add_plugin(menu::MenuPlugin);
         app.add_plugin(cats::CatPlugin);
         app.add_plugin(grid::HexGridPlugin);
         app.add_plugin(players::PlayerPlugin);
 
         app.add_plugin(gameplay::GamePlayPlugin);
+        app.add_plugin(accessibility::AccessibilityPlugin);
+        app.add_plugin(history::HistoryPlugin);
+        app.add_plugin(profiles::ProfilesPlugin);
+        app.add_plugin(modes::ModesPlugin);
 
         app.add_startup_system(setup);
-        app.add_system(reset_game);
+        app.add_system(reset_game.run_if(menu::key_bindings_active));
     }
 }
 
@@ -46,13 +74,15 @@ fn setup(mut commands: Commands) {
         brightness: 0.1,
         ..default()
     });
-    let transform = Transform::from_xyz(0.0, 40.0, 40.0).looking_at(Vec3::ZERO, Vec3::Y);
+    let orbit = camera::OrbitCamera::default();
+    let transform = orbit.transform();
     commands.spawn((
         Camera3dBundle {
             transform,
             ..default()
         },
         RaycastPickCamera::default(),
+        orbit,
     ));
     commands.spawn(DirectionalLightBundle {
         transform,
@@ -60,12 +90,18 @@ fn setup(mut commands: Commands) {
     });
 }
 
+/// Restart with the same configuration
 fn reset_game(
     keys: Res<Input<KeyCode>>,
+    settings: Res<settings::Settings>,
+    state: Res<State<GameState>>,
     mut reset_game: EventWriter<events::ResetGameEvent>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    if keys.just_pressed(KeyCode::R) {
+    if matches!(state.0, GameState::Loading | GameState::Menu) {
+        return;
+    }
+    if keys.just_pressed(settings.keys.restart) {
         reset_game.send(events::ResetGameEvent);
         next_state.set(GameState::Playing);
     }
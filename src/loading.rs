@@ -1,6 +1,10 @@
 use std::time::Duration;
 
-use crate::GameState;
+use crate::{
+    locale::{Language, Translations},
+    puzzles::PuzzleSet,
+    GameState,
+};
 use bevy::{gltf::Gltf, prelude::*};
 use bevy_asset_loader::prelude::*;
 use bevy_tweening::{
@@ -12,11 +16,14 @@ pub struct LoadingPlugin;
 impl Plugin for LoadingPlugin {
     fn build(&self, app: &mut App) {
         app.add_loading_state(
-            LoadingState::new(GameState::Loading).continue_to_state(GameState::Playing),
+            LoadingState::new(GameState::Loading).continue_to_state(GameState::Menu),
         );
         app.add_collection_to_loading_state::<_, FontAssets>(GameState::Loading);
         app.add_collection_to_loading_state::<_, CatModel>(GameState::Loading);
         app.add_collection_to_loading_state::<_, AudioAssets>(GameState::Loading);
+        app.add_collection_to_loading_state::<_, MusicAssets>(GameState::Loading);
+        app.add_collection_to_loading_state::<_, LocaleAssets>(GameState::Loading);
+        app.add_collection_to_loading_state::<_, PuzzleAssets>(GameState::Loading);
 
         app.add_system(spawn_loading_animation.in_schedule(OnEnter(GameState::Loading)));
         app.add_system(remove_loading_animation.in_schedule(OnExit(GameState::Loading)));
@@ -108,6 +115,18 @@ pub struct FontAssets {
 pub struct AudioAssets {
     #[asset(path = "sounds/boop.ogg")]
     pub boop: Handle<AudioSource>,
+    #[asset(path = "sounds/place.wav")]
+    pub place: Handle<AudioSource>,
+    #[asset(path = "sounds/fall.wav")]
+    pub fall: Handle<AudioSource>,
+    #[asset(path = "sounds/graduate.wav")]
+    pub graduate: Handle<AudioSource>,
+    #[asset(path = "sounds/win.wav")]
+    pub win: Handle<AudioSource>,
+    #[asset(path = "sounds/tie.wav")]
+    pub tie: Handle<AudioSource>,
+    #[asset(path = "sounds/too-slow.wav")]
+    pub too_slow: Handle<AudioSource>,
 }
 
 #[derive(Debug, Default, AssetCollection, Resource, Reflect)]
@@ -116,3 +135,37 @@ pub struct CatModel {
     #[asset(path = "models/cats.glb")]
     pub mesh: Handle<Gltf>,
 }
+
+#[derive(Debug, Default, AssetCollection, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct MusicAssets {
+    #[asset(path = "music/menu.wav")]
+    pub menu: Handle<AudioSource>,
+    #[asset(path = "music/game.wav")]
+    pub game: Handle<AudioSource>,
+    #[asset(path = "music/tension.wav")]
+    pub tension: Handle<AudioSource>,
+}
+
+#[derive(Debug, Default, AssetCollection, Resource)]
+pub struct LocaleAssets {
+    #[asset(path = "locales/en.ftl")]
+    pub en: Handle<Translations>,
+    #[asset(path = "locales/de.ftl")]
+    pub de: Handle<Translations>,
+}
+
+impl LocaleAssets {
+    pub fn get(&self, language: Language) -> &Handle<Translations> {
+        match language {
+            Language::English => &self.en,
+            Language::German => &self.de,
+        }
+    }
+}
+
+#[derive(Debug, Default, AssetCollection, Resource)]
+pub struct PuzzleAssets {
+    #[asset(path = "puzzles/win-in-n.puzzles.ron")]
+    pub puzzles: Handle<PuzzleSet>,
+}
@@ -0,0 +1,422 @@
+//! The part of the [Fluent](https://projectfluent.org) syntax the game's
+//! translations use: messages with variables and plural selectors.
+//!
+//! ```ftl
+//! # comment
+//! hello = Hello, { $name }!
+//! kittens = { $count ->
+//!     [one] one kitten
+//!    *[other] { $count } kittens
+//! }
+//! ```
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    reflect::TypeUuid,
+    utils::{BoxedFuture, HashMap},
+};
+
+/// Messages of one language, by id
+#[derive(Debug, TypeUuid)]
+#[uuid = "6c1d5b6e-6a4f-4c57-9a39-2b0c2f2b8d4e"]
+pub struct Translations {
+    messages: HashMap<String, Pattern>,
+    plural_category: fn(i64) -> &'static str,
+}
+
+type Pattern = Vec<Part>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Text(String),
+    Variable(String),
+    Select {
+        variable: String,
+        variants: Vec<(String, Pattern)>,
+        /// Variant used when no other matches (marked with `*`)
+        default: usize,
+    },
+}
+
+/// Value for a `{ $variable }` in a message
+#[derive(Debug, Clone, PartialEq)]
+pub enum Arg {
+    Number(i64),
+    Text(String),
+}
+
+impl From<&str> for Arg {
+    fn from(text: &str) -> Self {
+        Arg::Text(text.to_string())
+    }
+}
+
+impl From<String> for Arg {
+    fn from(text: String) -> Self {
+        Arg::Text(text)
+    }
+}
+
+macro_rules! number_arg {
+    ($($ty:ty),*) => {
+        $(impl From<$ty> for Arg {
+            fn from(number: $ty) -> Self {
+                Arg::Number(number as i64)
+            }
+        })*
+    };
+}
+number_arg!(u8, u32, usize, i32);
+
+impl std::fmt::Display for Arg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Arg::Number(number) => write!(f, "{number}"),
+            Arg::Text(text) => f.write_str(text),
+        }
+    }
+}
+
+impl Translations {
+    /// Messages in `source`, for the language with the code `language`
+    /// (e.g. `en`), which decides how numbers select plural variants
+    pub fn parse(source: &str, language: &str) -> Result<Self, String> {
+        let plural_category = plural_rule(language)?;
+        let mut messages = HashMap::default();
+        let mut current: Option<(String, Vec<&str>)> = None;
+
+        let mut finish = |current: Option<(String, Vec<&str>)>| -> Result<(), String> {
+            if let Some((id, lines)) = current {
+                let pattern = parse_pattern(&lines.join("\n"))
+                    .map_err(|error| format!("in message `{id}`: {error}"))?;
+                messages.insert(id, pattern);
+            }
+            Ok(())
+        };
+
+        for (number, line) in source.lines().enumerate() {
+            let continued = line.starts_with(char::is_whitespace) || line.starts_with('}');
+            if continued && current.is_some() && !line.trim().is_empty() {
+                if let Some((_, lines)) = current.as_mut() {
+                    lines.push(line.trim());
+                }
+                continue;
+            }
+            if line.starts_with('#') {
+                // comments end the message before them
+                finish(current.take())?;
+                continue;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Some((id, value)) = line.split_once('=') else {
+                return Err(format!("line {}: expected `id = value`", number + 1));
+            };
+            finish(current.take())?;
+            // a value starting on the next line doesn't start with a newline
+            let lines = match value.trim() {
+                "" => Vec::new(),
+                value => vec![value],
+            };
+            current = Some((id.trim().to_string(), lines));
+        }
+        finish(current)?;
+
+        Ok(Self {
+            messages,
+            plural_category,
+        })
+    }
+
+    /// Message `id` with its variables filled in from `args`
+    pub fn format(&self, id: &str, args: &[(&str, Arg)]) -> Option<String> {
+        let pattern = self.messages.get(id)?;
+        let mut out = String::new();
+        format_pattern(pattern, args, self.plural_category, &mut out);
+        Some(out)
+    }
+}
+
+fn format_pattern(
+    pattern: &[Part],
+    args: &[(&str, Arg)],
+    plural_category: fn(i64) -> &'static str,
+    out: &mut String,
+) {
+    let arg = |name: &str| {
+        args.iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, arg)| arg)
+    };
+    for part in pattern {
+        match part {
+            Part::Text(text) => out.push_str(text),
+            Part::Variable(name) => match arg(name) {
+                Some(value) => out.push_str(&value.to_string()),
+                None => {
+                    out.push('$');
+                    out.push_str(name);
+                }
+            },
+            Part::Select {
+                variable,
+                variants,
+                default,
+            } => {
+                let value = arg(variable);
+                let exact = value.map(|value| value.to_string());
+                let category = match value {
+                    Some(Arg::Number(number)) => Some(plural_category(*number)),
+                    _ => None,
+                };
+                let chosen = variants
+                    .iter()
+                    .find(|(key, _)| Some(key) == exact.as_ref())
+                    .or_else(|| {
+                        variants
+                            .iter()
+                            .find(|(key, _)| Some(key.as_str()) == category)
+                    })
+                    .unwrap_or(&variants[*default]);
+                format_pattern(&chosen.1, args, plural_category, out);
+            }
+        }
+    }
+}
+
+/// CLDR plural rule of the language with the code `language`; every
+/// translation needs one here, so numbers never pick the wrong variant
+fn plural_rule(language: &str) -> Result<fn(i64) -> &'static str, String> {
+    match language {
+        "en" | "de" => Ok(one_other),
+        _ => Err(format!("no plural rule for language `{language}`")),
+    }
+}
+
+/// `one` for 1 and `other` for everything else, e.g. English and German
+fn one_other(number: i64) -> &'static str {
+    if number == 1 {
+        "one"
+    } else {
+        "other"
+    }
+}
+
+fn parse_pattern(source: &str) -> Result<Pattern, String> {
+    let mut parts = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            parts.push(Part::Text(rest[..start].to_string()));
+        }
+        let end = matching_brace(&rest[start..]).ok_or("unclosed `{`")? + start;
+        parts.push(parse_placeable(rest[start + 1..end].trim())?);
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Text(rest.to_string()));
+    }
+    Ok(parts)
+}
+
+/// Index of the `}` closing the `{` at the start of `source`
+fn matching_brace(source: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (idx, char) in source.char_indices() {
+        match char {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_placeable(source: &str) -> Result<Part, String> {
+    // string literals, e.g. `{ "[" }` for text that would be a variant key
+    if let Some(text) = source
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+    {
+        return Ok(Part::Text(text.to_string()));
+    }
+    let Some((selector, body)) = source.split_once("->") else {
+        let name = source
+            .strip_prefix('$')
+            .ok_or_else(|| format!("expected a variable, found `{source}`"))?;
+        return Ok(Part::Variable(name.to_string()));
+    };
+
+    let variable = selector
+        .trim()
+        .strip_prefix('$')
+        .ok_or_else(|| format!("expected a variable to select on, found `{selector}`"))?
+        .to_string();
+    let mut variants = Vec::new();
+    let mut default = None;
+    for line in body.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let (is_default, line) = match line.strip_prefix('*') {
+            Some(line) => (true, line),
+            None => (false, line),
+        };
+        let (key, value) = line
+            .strip_prefix('[')
+            .and_then(|line| line.split_once(']'))
+            .ok_or_else(|| format!("expected `[key] value`, found `{line}`"))?;
+        if is_default {
+            default = Some(variants.len());
+        }
+        variants.push((key.trim().to_string(), parse_pattern(value.trim())?));
+    }
+    let default = default.ok_or("select expression without `*` default variant")?;
+    Ok(Part::Select {
+        variable,
+        variants,
+        default,
+    })
+}
+
+#[derive(Default)]
+pub struct FtlLoader;
+
+impl AssetLoader for FtlLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let source = std::str::from_utf8(bytes)?;
+            // files are named after their language, e.g. `en.ftl`
+            let language = load_context
+                .path()
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or_default();
+            let translations = Translations::parse(source, language).map_err(|error| {
+                bevy::asset::Error::msg(format!("{}: {error}", load_context.path().display()))
+            })?;
+            load_context.set_default_asset(LoadedAsset::new(translations));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ftl"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(source: &str, id: &str, args: &[(&str, Arg)]) -> Option<String> {
+        Translations::parse(source, "en").unwrap().format(id, args)
+    }
+
+    #[test]
+    fn plain_messages() {
+        let source = "hello = Hello, world!\nbye=Bye";
+        assert_eq!(format(source, "hello", &[]).unwrap(), "Hello, world!");
+        assert_eq!(format(source, "bye", &[]).unwrap(), "Bye");
+        assert_eq!(format(source, "missing", &[]), None);
+    }
+
+    #[test]
+    fn multiline_messages() {
+        let source = "rules =\n    First line\n    second line\n\nnext = Next";
+        assert_eq!(
+            format(source, "rules", &[]).unwrap(),
+            "First line\nsecond line"
+        );
+        assert_eq!(format(source, "next", &[]).unwrap(), "Next");
+    }
+
+    #[test]
+    fn variables() {
+        let source = "hello = Hello, { $name }, you have {$count} points";
+        assert_eq!(
+            format(
+                source,
+                "hello",
+                &[("name", "Tom".into()), ("count", 3.into())]
+            )
+            .unwrap(),
+            "Hello, Tom, you have 3 points"
+        );
+        assert_eq!(
+            format(source, "hello", &[]).unwrap(),
+            "Hello, $name, you have $count points"
+        );
+        let source = r#"key = { "[" }Space{ "]" }"#;
+        assert_eq!(format(source, "key", &[]).unwrap(), "[Space]");
+    }
+
+    #[test]
+    fn plural_selectors() {
+        let source = "kittens = { $count ->
+    [0] no kittens
+    [one] one kitten
+   *[other] { $count } kittens
+}";
+        let kittens = |count: u32| format(source, "kittens", &[("count", count.into())]).unwrap();
+        assert_eq!(kittens(0), "no kittens");
+        assert_eq!(kittens(1), "one kitten");
+        assert_eq!(kittens(5), "5 kittens");
+        assert_eq!(format(source, "kittens", &[]).unwrap(), "$count kittens");
+    }
+
+    #[test]
+    fn text_selectors() {
+        let source = "turn = { $kind ->
+    [human] Your turn
+   *[other] Thinking
+}";
+        let turn = |kind: &str| format(source, "turn", &[("kind", kind.into())]).unwrap();
+        assert_eq!(turn("human"), "Your turn");
+        assert_eq!(turn("computer"), "Thinking");
+    }
+
+    #[test]
+    fn comments_end_messages() {
+        let source = "# heading
+first = One
+    more
+# about the next message
+    second = Two
+";
+        let translations = Translations::parse(source, "en").unwrap();
+        assert_eq!(translations.format("first", &[]).unwrap(), "One\nmore");
+        assert_eq!(translations.format("second", &[]).unwrap(), "Two");
+    }
+
+    #[test]
+    fn errors() {
+        assert!(Translations::parse("no value here", "en").is_err());
+        assert!(Translations::parse("open = { $name", "en").is_err());
+        assert!(Translations::parse("select = { $n ->\n [one] x\n}", "en").is_err());
+    }
+
+    #[test]
+    fn languages_need_plural_rules() {
+        assert!(Translations::parse("hello = Hello", "de").is_ok());
+        let error = Translations::parse("hello = Hello", "pl").unwrap_err();
+        assert!(error.contains("pl"), "{error}");
+    }
+
+    #[test]
+    fn shipped_translations_parse() {
+        for (language, source) in [
+            ("en", include_str!("../../assets/locales/en.ftl")),
+            ("de", include_str!("../../assets/locales/de.ftl")),
+        ] {
+            Translations::parse(source, language).unwrap();
+        }
+    }
+}
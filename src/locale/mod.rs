@@ -0,0 +1,130 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{config::GameConfig, loading::LocaleAssets, settings::Settings};
+
+mod fluent;
+
+pub use fluent::{Arg, Translations};
+
+pub struct LocalePlugin;
+
+impl Plugin for LocalePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Translations>();
+        app.init_asset_loader::<fluent::FtlLoader>();
+        app.add_system(rename_default_players.run_if(resource_exists::<LocaleAssets>()));
+    }
+}
+
+/// Language of the user interface, translations are in `assets/locales`
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, FromReflect, Serialize, Deserialize,
+)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub fn cycle(&mut self) {
+        *self = match self {
+            Language::English => Language::German,
+            Language::German => Language::English,
+        };
+    }
+
+    /// Name of the language in itself, so it can be found without knowing
+    /// the current one
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+}
+
+/// Translated text for the current language
+#[derive(SystemParam)]
+pub struct Locale<'w> {
+    settings: Res<'w, Settings>,
+    /// Missing while loading
+    assets: Option<Res<'w, LocaleAssets>>,
+    translations: Res<'w, Assets<Translations>>,
+}
+
+impl<'w> Locale<'w> {
+    /// Message `id` without any variables
+    pub fn t(&self, id: &str) -> String {
+        self.t_args(id, &[])
+    }
+
+    /// Message `id` with its variables filled in, falling back to English
+    /// and then to the id itself when there is no translation
+    pub fn t_args(&self, id: &str, args: &[(&str, Arg)]) -> String {
+        let Some(assets) = &self.assets else {
+            return id.to_string();
+        };
+        translate(&self.translations, assets, self.settings.language, id, args)
+    }
+}
+
+fn translate(
+    translations: &Assets<Translations>,
+    assets: &LocaleAssets,
+    language: Language,
+    id: &str,
+    args: &[(&str, Arg)],
+) -> String {
+    [assets.get(language), assets.get(Language::English)]
+        .into_iter()
+        .filter_map(|handle| translations.get(handle))
+        .find_map(|translations| translations.format(id, args))
+        .unwrap_or_else(|| {
+            warn!(?id, "Missing translation");
+            id.to_string()
+        })
+}
+
+/// Default name of the player in this seat
+pub fn default_player_name(
+    translations: &Assets<Translations>,
+    assets: &LocaleAssets,
+    language: Language,
+    idx: usize,
+) -> String {
+    match idx {
+        0 => translate(translations, assets, language, "player-green", &[]),
+        1 => translate(translations, assets, language, "player-orange", &[]),
+        _ => translate(
+            translations,
+            assets,
+            language,
+            "player-numbered",
+            &[("number", (idx + 1).into())],
+        ),
+    }
+}
+
+/// Translate player names nobody changed when switching languages
+#[instrument(level = "debug", skip_all)]
+fn rename_default_players(
+    settings: Res<Settings>,
+    assets: Res<LocaleAssets>,
+    translations: Res<Assets<Translations>>,
+    mut config: ResMut<GameConfig>,
+    mut applied: Local<Language>,
+) {
+    if settings.language == *applied {
+        return;
+    }
+    for (idx, player) in config.players.iter_mut().enumerate() {
+        let old = default_player_name(&translations, &assets, *applied, idx);
+        if player.name == old {
+            player.name = default_player_name(&translations, &assets, settings.language, idx);
+        }
+    }
+    *applied = settings.language;
+}
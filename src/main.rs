@@ -14,6 +14,7 @@ fn main() {
                 title: "B⬡⬡P".to_string(), // ToDo
                 resolution: (800., 600.).into(),
                 canvas: Some("#bevy".to_owned()),
+                fit_canvas_to_parent: true,
                 ..default()
             }),
             ..default()
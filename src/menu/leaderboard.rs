@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+
+use crate::{
+    config::PlayerKind,
+    loading::FontAssets,
+    locale::Locale,
+    profiles::{Rated, Ratings},
+    settings::Settings,
+};
+
+use super::{
+    widgets::{button, label, row, screen, title},
+    MenuButton,
+};
+
+/// Height of the highest bar in the rating history
+const HISTORY_HEIGHT: f32 = 80.;
+
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct LeaderboardMenu;
+
+#[derive(Debug, Default, Resource)]
+pub struct LeaderboardMenuState {
+    pub open: bool,
+    /// Place on the leaderboard whose rating history is shown
+    pub selected: Option<usize>,
+}
+
+pub fn leaderboard_menu_outdated(
+    ratings: Res<Ratings>,
+    settings: Res<Settings>,
+    state: Res<LeaderboardMenuState>,
+) -> bool {
+    state.is_changed() || (state.open && (ratings.is_changed() || settings.is_changed()))
+}
+
+/// Everyone's rating, best first, on top of the main menu
+pub fn spawn_leaderboard_menu(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Locale,
+    ratings: Res<Ratings>,
+    state: Res<LeaderboardMenuState>,
+    previous: Query<Entity, With<LeaderboardMenu>>,
+) {
+    for entity in previous.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if !state.open {
+        return;
+    }
+    let leaderboard = ratings.leaderboard();
+
+    commands
+        .spawn((
+            NodeBundle {
+                z_index: ZIndex::Global(10),
+                ..screen(Color::WHITE.with_a(0.9))
+            },
+            LeaderboardMenu,
+        ))
+        .with_children(|parent| {
+            title(parent, &fonts, locale.t("leaderboard-title"));
+
+            if leaderboard.is_empty() {
+                label(parent, &fonts, locale.t("leaderboard-empty"));
+            }
+            for (place, rating) in leaderboard.iter().enumerate() {
+                row(parent, |parent| {
+                    label(parent, &fonts, format!("{}.", place + 1));
+                    button(
+                        parent,
+                        &fonts,
+                        rated_name(&locale, &rating.who),
+                        MenuButton::SelectRating(place),
+                    );
+                    label(
+                        parent,
+                        &fonts,
+                        locale.t_args(
+                            "leaderboard-rating",
+                            &[
+                                ("rating", (rating.rating.round() as i32).into()),
+                                ("games", rating.games.into()),
+                            ],
+                        ),
+                    );
+                });
+            }
+
+            if let Some(rating) = state.selected.and_then(|place| leaderboard.get(place)) {
+                label(
+                    parent,
+                    &fonts,
+                    locale.t_args(
+                        "leaderboard-history",
+                        &[("player", rated_name(&locale, &rating.who).into())],
+                    ),
+                );
+                history_chart(parent, &rating.history);
+            }
+
+            button(
+                parent,
+                &fonts,
+                locale.t("settings-back"),
+                MenuButton::CloseLeaderboard,
+            );
+        });
+}
+
+fn rated_name(locale: &Locale, who: &Rated) -> String {
+    match who {
+        Rated::Player(name) => name.clone(),
+        Rated::Computer => locale.t(PlayerKind::Computer.label_id()),
+    }
+}
+
+/// One bar per game, scaled between the lowest and the highest rating
+fn history_chart(parent: &mut ChildBuilder, history: &[f32]) {
+    let lowest = history.iter().copied().fold(f32::INFINITY, f32::min);
+    let highest = history.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let range = (highest - lowest).max(1.);
+
+    parent
+        .spawn((NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::FlexEnd,
+                size: Size::height(Val::Px(HISTORY_HEIGHT)),
+                gap: Size::width(Val::Px(2.)),
+                ..default()
+            },
+            ..default()
+        },))
+        .with_children(|parent| {
+            for rating in history {
+                // the lowest game still gets a visible bar
+                let height = 0.1 + 0.9 * (rating - lowest) / range;
+                parent.spawn((NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(6.), Val::Px(HISTORY_HEIGHT * height)),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(Color::DARK_GRAY),
+                    ..default()
+                },));
+            }
+        });
+}
@@ -0,0 +1,315 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use tracing::instrument;
+
+use crate::{
+    accessibility::MoveEntry,
+    config::{GameConfig, MAX_MAP_RADIUS, MIN_MAP_RADIUS},
+    events::ResetGameEvent,
+    grid::MapSettings,
+    loading::{LocaleAssets, PuzzleAssets},
+    locale::{default_player_name, Translations},
+    modes::{analysis::StartAnalysis, puzzle::StartPuzzle, tutorial::StartTutorial},
+    profiles::Profiles,
+    settings::{KeyAction, Settings, MAX_ANIMATION_SPEED, MIN_ANIMATION_SPEED},
+    GameState,
+};
+
+use self::{
+    leaderboard::LeaderboardMenuState, pause::PauseMenuState, profiles::ProfilesMenuState,
+    puzzles::PuzzlesMenuState, settings::SettingsMenuState,
+};
+
+mod leaderboard;
+mod pause;
+mod profiles;
+mod puzzles;
+mod settings;
+mod setup;
+mod widgets;
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameConfig>();
+        app.register_type::<GameConfig>();
+        app.init_resource::<NameFocus>();
+        app.init_resource::<SettingsMenuState>();
+        app.init_resource::<ProfilesMenuState>();
+        app.init_resource::<LeaderboardMenuState>();
+        app.init_resource::<PuzzlesMenuState>();
+        app.init_resource::<PauseMenuState>();
+
+        app.add_system(setup::spawn_setup_menu.in_schedule(OnEnter(GameState::Menu)));
+        app.add_system(cleanup::<setup::SetupMenu>.in_schedule(OnExit(GameState::Menu)));
+        app.add_systems(
+            (
+                setup::spawn_setup_menu.run_if(setup::setup_menu_outdated),
+                setup::edit_name,
+            )
+                .in_set(OnUpdate(GameState::Menu)),
+        );
+
+        app.add_system(pause::spawn_pause_menu.in_schedule(OnEnter(GameState::Paused)));
+        app.add_system(
+            pause::spawn_pause_menu
+                .run_if(
+                    resource_changed::<Settings>().or_else(resource_changed::<PauseMenuState>()),
+                )
+                .in_set(OnUpdate(GameState::Paused)),
+        );
+        app.add_system(cleanup::<pause::PauseMenu>.in_schedule(OnExit(GameState::Paused)));
+        app.add_system(close_pause.in_schedule(OnExit(GameState::Paused)));
+        app.add_system(pause::toggle_pause.run_if(key_bindings_active));
+
+        app.add_systems((
+            settings::spawn_settings_menu.run_if(settings::settings_menu_outdated),
+            settings::capture_key,
+        ));
+        app.add_system(close_settings.in_schedule(OnExit(GameState::Menu)));
+        app.add_system(close_settings.in_schedule(OnExit(GameState::Paused)));
+
+        app.add_system(profiles::spawn_profiles_menu.run_if(profiles::profiles_menu_outdated));
+        app.add_system(close_profiles.in_schedule(OnExit(GameState::Menu)));
+        app.add_system(
+            leaderboard::spawn_leaderboard_menu.run_if(leaderboard::leaderboard_menu_outdated),
+        );
+        app.add_system(close_leaderboard.in_schedule(OnExit(GameState::Menu)));
+        app.add_system(
+            puzzles::spawn_puzzles_menu
+                .run_if(resource_exists::<PuzzleAssets>())
+                .run_if(puzzles::puzzles_menu_outdated),
+        );
+        app.add_system(close_puzzles.in_schedule(OnExit(GameState::Menu)));
+
+        app.add_systems((menu_buttons, widgets::highlight_buttons));
+    }
+}
+
+/// Key bindings don't apply while the settings menu is open, where a key
+/// might be about to be bound to something else, or while a player name or a
+/// move is typed in
+pub fn key_bindings_active(
+    settings_menu: Res<SettingsMenuState>,
+    name_focus: Res<NameFocus>,
+    entry: Res<MoveEntry>,
+) -> bool {
+    !settings_menu.open
+        && settings_menu.rebinding.is_none()
+        && name_focus.0.is_none()
+        && entry.text.is_none()
+}
+
+/// Player whose name is currently being typed in the setup menu
+#[derive(Debug, Default, Resource)]
+pub struct NameFocus(pub Option<usize>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub enum MenuButton {
+    RadiusDown,
+    RadiusUp,
+    PlayersDown,
+    PlayersUp,
+    EditName(usize),
+    CycleColor(usize),
+    TogglePlayerKind(usize),
+    /// Play as the next saved profile
+    CycleProfile(usize),
+    ToggleRules,
+    CycleTimeControl,
+    CycleSeries,
+    Start,
+    Tutorial,
+    OpenPuzzles,
+    ClosePuzzles,
+    /// Play the puzzle with this index
+    StartPuzzle(usize),
+    /// Set up positions on an empty board
+    Analysis,
+    Resume,
+    Restart,
+    /// Look into the position of the game being played, which ends it
+    Analyze,
+    ConfirmAnalyze,
+    CancelAnalyze,
+    MainMenu,
+    OpenSettings,
+    CloseSettings,
+    OpenProfiles,
+    CloseProfiles,
+    PreviousProfile,
+    NextProfile,
+    OpenLeaderboard,
+    CloseLeaderboard,
+    /// Show the rating history of the player at this place
+    SelectRating(usize),
+    VolumeDown,
+    VolumeUp,
+    ToggleMute,
+    MusicVolumeDown,
+    MusicVolumeUp,
+    ToggleFullscreen,
+    CycleResolution,
+    AnimationSpeedDown,
+    AnimationSpeedUp,
+    CyclePalette,
+    TogglePieceMarkers,
+    CycleLanguage,
+    Rebind(KeyAction),
+}
+
+/// Events that start one of the other game modes
+#[derive(SystemParam)]
+struct ModeStarts<'w> {
+    tutorial: EventWriter<'w, StartTutorial>,
+    puzzle: EventWriter<'w, StartPuzzle>,
+    analysis: EventWriter<'w, StartAnalysis>,
+}
+
+#[instrument(level = "debug", skip_all)]
+fn menu_buttons(
+    buttons: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut config: ResMut<GameConfig>,
+    mut map_settings: ResMut<MapSettings>,
+    mut settings: ResMut<Settings>,
+    mut settings_menu: ResMut<SettingsMenuState>,
+    mut profiles_menu: ResMut<ProfilesMenuState>,
+    mut leaderboard_menu: ResMut<LeaderboardMenuState>,
+    mut puzzles_menu: ResMut<PuzzlesMenuState>,
+    mut pause_menu: ResMut<PauseMenuState>,
+    profiles: Res<Profiles>,
+    locale_assets: Option<Res<LocaleAssets>>,
+    translations: Res<Assets<Translations>>,
+    mut focus: ResMut<NameFocus>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut reset: EventWriter<ResetGameEvent>,
+    mut modes: ModeStarts,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        debug!(?button, "Menu button clicked");
+
+        focus.0 = None;
+        match *button {
+            MenuButton::RadiusDown => {
+                map_settings.map_radius = map_settings
+                    .map_radius
+                    .saturating_sub(1)
+                    .max(MIN_MAP_RADIUS);
+            }
+            MenuButton::RadiusUp => {
+                map_settings.map_radius = (map_settings.map_radius + 1).min(MAX_MAP_RADIUS);
+            }
+            MenuButton::PlayersDown => config.remove_player(),
+            MenuButton::PlayersUp => {
+                let idx = config.players.len();
+                config.add_player(settings.palette);
+                if let (Some(player), Some(assets)) = (config.players.get_mut(idx), &locale_assets)
+                {
+                    player.name =
+                        default_player_name(&translations, assets, settings.language, idx);
+                }
+            }
+            MenuButton::EditName(idx) => focus.0 = Some(idx),
+            MenuButton::CycleColor(idx) => config.players[idx].cycle_color(settings.palette),
+            MenuButton::TogglePlayerKind(idx) => config.players[idx].kind.toggle(),
+            MenuButton::CycleProfile(idx) => {
+                let player = &mut config.players[idx];
+                let Some(profile) = profiles.next_after(&player.name) else {
+                    continue;
+                };
+                player.name = profile.name.clone();
+                let colors = settings.palette.colors();
+                player.color = colors[profile.color % colors.len()];
+            }
+            MenuButton::ToggleRules => config.rules.toggle(),
+            MenuButton::CycleTimeControl => config.time_control.cycle(),
+            MenuButton::CycleSeries => config.series.cycle(),
+            MenuButton::Start | MenuButton::Restart => {
+                reset.send(ResetGameEvent);
+                next_state.set(GameState::Playing);
+            }
+            MenuButton::Tutorial => modes.tutorial.send(StartTutorial),
+            MenuButton::OpenPuzzles => puzzles_menu.open = true,
+            MenuButton::ClosePuzzles => puzzles_menu.open = false,
+            MenuButton::StartPuzzle(idx) => modes.puzzle.send(StartPuzzle(idx)),
+            MenuButton::Analysis => modes.analysis.send(StartAnalysis {
+                current_game: false,
+            }),
+            MenuButton::Analyze => pause_menu.confirm_analyze = true,
+            MenuButton::ConfirmAnalyze => modes.analysis.send(StartAnalysis { current_game: true }),
+            MenuButton::CancelAnalyze => pause_menu.confirm_analyze = false,
+            MenuButton::Resume => next_state.set(GameState::Playing),
+            MenuButton::MainMenu => next_state.set(GameState::Menu),
+            MenuButton::OpenSettings => settings_menu.open = true,
+            MenuButton::CloseSettings => {
+                settings_menu.open = false;
+                settings_menu.rebinding = None;
+            }
+            MenuButton::OpenProfiles => profiles_menu.open = true,
+            MenuButton::CloseProfiles => profiles_menu.open = false,
+            MenuButton::PreviousProfile => {
+                let count = profiles.profiles.len().max(1);
+                profiles_menu.selected = (profiles_menu.selected + count - 1) % count;
+            }
+            MenuButton::NextProfile => {
+                profiles_menu.selected =
+                    (profiles_menu.selected + 1) % profiles.profiles.len().max(1);
+            }
+            MenuButton::VolumeDown => settings.volume = (settings.volume - 0.1).max(0.),
+            MenuButton::VolumeUp => settings.volume = (settings.volume + 0.1).min(1.),
+            MenuButton::ToggleMute => settings.muted = !settings.muted,
+            MenuButton::MusicVolumeDown => {
+                settings.music_volume = (settings.music_volume - 0.1).max(0.);
+            }
+            MenuButton::MusicVolumeUp => {
+                settings.music_volume = (settings.music_volume + 0.1).min(1.);
+            }
+            MenuButton::ToggleFullscreen => settings.fullscreen = !settings.fullscreen,
+            MenuButton::CycleResolution => settings.cycle_resolution(),
+            MenuButton::AnimationSpeedDown => {
+                settings.animation_speed =
+                    (settings.animation_speed - 0.25).max(MIN_ANIMATION_SPEED);
+            }
+            MenuButton::AnimationSpeedUp => {
+                settings.animation_speed =
+                    (settings.animation_speed + 0.25).min(MAX_ANIMATION_SPEED);
+            }
+            MenuButton::CyclePalette => settings.palette.cycle(),
+            MenuButton::TogglePieceMarkers => settings.piece_markers = !settings.piece_markers,
+            MenuButton::CycleLanguage => settings.language.cycle(),
+            MenuButton::OpenLeaderboard => leaderboard_menu.open = true,
+            MenuButton::CloseLeaderboard => *leaderboard_menu = LeaderboardMenuState::default(),
+            MenuButton::SelectRating(place) => leaderboard_menu.selected = Some(place),
+            MenuButton::Rebind(action) => settings_menu.rebinding = Some(action),
+        }
+    }
+}
+
+fn close_settings(mut settings_menu: ResMut<SettingsMenuState>) {
+    *settings_menu = SettingsMenuState::default();
+}
+
+fn close_profiles(mut profiles_menu: ResMut<ProfilesMenuState>) {
+    *profiles_menu = ProfilesMenuState::default();
+}
+
+fn close_leaderboard(mut leaderboard_menu: ResMut<LeaderboardMenuState>) {
+    *leaderboard_menu = LeaderboardMenuState::default();
+}
+
+fn close_pause(mut pause_menu: ResMut<PauseMenuState>) {
+    *pause_menu = PauseMenuState::default();
+}
+
+fn close_puzzles(mut puzzles_menu: ResMut<PuzzlesMenuState>) {
+    *puzzles_menu = PuzzlesMenuState::default();
+}
+
+fn cleanup<T: Component>(mut commands: Commands, query: Query<Entity, With<T>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
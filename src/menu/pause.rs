@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+
+use crate::{loading::FontAssets, locale::Locale, modes::GameMode, settings::Settings, GameState};
+
+use super::{
+    widgets::{button, label, row, screen, title},
+    MenuButton,
+};
+
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct PauseMenu;
+
+#[derive(Debug, Default, Resource)]
+pub struct PauseMenuState {
+    /// Analyzing ends the game being played, so it has to be confirmed
+    pub confirm_analyze: bool,
+}
+
+pub fn toggle_pause(
+    keys: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !keys.just_pressed(settings.keys.pause) {
+        return;
+    }
+    match state.0 {
+        GameState::Playing => next_state.set(GameState::Paused),
+        GameState::Paused => next_state.set(GameState::Playing),
+        _ => {}
+    }
+}
+
+pub fn spawn_pause_menu(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Locale,
+    settings: Res<Settings>,
+    mode: Res<GameMode>,
+    state: Res<PauseMenuState>,
+    previous: Query<Entity, With<PauseMenu>>,
+) {
+    // also rebuilt when the language changes
+    for entity in previous.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands
+        .spawn((screen(Color::WHITE.with_a(0.6)), PauseMenu))
+        .with_children(|parent| {
+            title(parent, &fonts, locale.t("pause-title"));
+            button(parent, &fonts, locale.t("pause-resume"), MenuButton::Resume);
+            button(
+                parent,
+                &fonts,
+                locale.t("pause-restart"),
+                MenuButton::Restart,
+            );
+            if state.confirm_analyze {
+                label(parent, &fonts, locale.t("pause-analyze-warning"));
+                row(parent, |parent| {
+                    button(
+                        parent,
+                        &fonts,
+                        locale.t("pause-analyze-confirm"),
+                        MenuButton::ConfirmAnalyze,
+                    );
+                    button(
+                        parent,
+                        &fonts,
+                        locale.t("pause-analyze-cancel"),
+                        MenuButton::CancelAnalyze,
+                    );
+                });
+            } else if *mode != GameMode::Analysis {
+                button(
+                    parent,
+                    &fonts,
+                    locale.t("pause-analyze"),
+                    MenuButton::Analyze,
+                );
+            }
+            button(
+                parent,
+                &fonts,
+                locale.t("menu-settings"),
+                MenuButton::OpenSettings,
+            );
+            button(
+                parent,
+                &fonts,
+                locale.t("pause-main-menu"),
+                MenuButton::MainMenu,
+            );
+            label(
+                parent,
+                &fonts,
+                locale.t_args(
+                    "pause-resume-hint",
+                    &[("key", format!("{:?}", settings.keys.pause).into())],
+                ),
+            );
+        });
+}
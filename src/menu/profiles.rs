@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+
+use crate::{
+    accessibility::describe_hex,
+    loading::FontAssets,
+    locale::Locale,
+    profiles::{Achievement, Profile, Profiles},
+    settings::Settings,
+};
+
+use super::{
+    widgets::{button, colored_button, label, row, screen, title},
+    MenuButton,
+};
+
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct ProfilesMenu;
+
+#[derive(Debug, Default, Resource)]
+pub struct ProfilesMenuState {
+    pub open: bool,
+    /// Index of the profile on screen
+    pub selected: usize,
+}
+
+pub fn profiles_menu_outdated(
+    profiles: Res<Profiles>,
+    settings: Res<Settings>,
+    state: Res<ProfilesMenuState>,
+) -> bool {
+    state.is_changed() || (state.open && (profiles.is_changed() || settings.is_changed()))
+}
+
+/// Statistics of one saved profile at a time, shown on top of the main menu
+pub fn spawn_profiles_menu(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Locale,
+    profiles: Res<Profiles>,
+    settings: Res<Settings>,
+    state: Res<ProfilesMenuState>,
+    previous: Query<Entity, With<ProfilesMenu>>,
+) {
+    for entity in previous.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if !state.open {
+        return;
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                z_index: ZIndex::Global(10),
+                ..screen(Color::WHITE.with_a(0.9))
+            },
+            ProfilesMenu,
+        ))
+        .with_children(|parent| {
+            title(parent, &fonts, locale.t("profiles-title"));
+
+            match profiles.profiles.get(state.selected) {
+                Some(profile) => {
+                    row(parent, |parent| {
+                        button(parent, &fonts, "<", MenuButton::PreviousProfile);
+                        let colors = settings.palette.colors();
+                        let color = colors[profile.color % colors.len()];
+                        colored_button(
+                            parent,
+                            &fonts,
+                            profile.name.clone(),
+                            color,
+                            MenuButton::NextProfile,
+                        );
+                        button(parent, &fonts, ">", MenuButton::NextProfile);
+                    });
+                    for line in stats_lines(&locale, profile) {
+                        label(parent, &fonts, line);
+                    }
+                }
+                None => label(parent, &fonts, locale.t("profiles-empty")),
+            }
+
+            button(
+                parent,
+                &fonts,
+                locale.t("settings-back"),
+                MenuButton::CloseProfiles,
+            );
+        });
+}
+
+fn stats_lines(locale: &Locale, profile: &Profile) -> Vec<String> {
+    let stats = &profile.stats;
+    let mut lines = vec![
+        locale.t_args("profiles-games", &[("count", stats.games.into())]),
+        locale.t_args(
+            "profiles-results",
+            &[
+                ("wins", stats.wins.into()),
+                ("losses", stats.losses.into()),
+                ("ties", stats.ties.into()),
+            ],
+        ),
+        locale.t_args("profiles-boops", &[("count", stats.boops_dealt.into())]),
+        locale.t_args("profiles-cats-lost", &[("count", stats.cats_lost.into())]),
+    ];
+    if let Some(average) = stats.average_game_length() {
+        lines.push(locale.t_args(
+            "profiles-average-length",
+            &[("moves", format!("{average:.1}").into())],
+        ));
+    }
+    if let Some(opening) = stats.favorite_opening() {
+        lines.push(locale.t_args(
+            "profiles-favorite-opening",
+            &[("cell", describe_hex(opening).into())],
+        ));
+    }
+    for achievement in Achievement::ALL {
+        let id = if profile.achievements.contains(&achievement) {
+            "profiles-achievement-unlocked"
+        } else {
+            "profiles-achievement-locked"
+        };
+        lines.push(locale.t_args(
+            id,
+            &[
+                ("name", locale.t(achievement.label_id()).into()),
+                ("description", locale.t(achievement.description_id()).into()),
+            ],
+        ));
+    }
+    lines
+}
@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+
+use crate::{
+    loading::{FontAssets, PuzzleAssets},
+    locale::Locale,
+    modes::puzzle::PuzzleProgress,
+    puzzles::PuzzleSet,
+    settings::Settings,
+};
+
+use super::{
+    widgets::{button, label, row, screen, title},
+    MenuButton,
+};
+
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct PuzzlesMenu;
+
+#[derive(Debug, Default, Resource)]
+pub struct PuzzlesMenuState {
+    pub open: bool,
+}
+
+pub fn puzzles_menu_outdated(
+    progress: Res<PuzzleProgress>,
+    settings: Res<Settings>,
+    state: Res<PuzzlesMenuState>,
+) -> bool {
+    state.is_changed() || (state.open && (progress.is_changed() || settings.is_changed()))
+}
+
+/// All puzzles with the ones already solved marked, on top of the main menu
+pub fn spawn_puzzles_menu(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Locale,
+    assets: Res<PuzzleAssets>,
+    puzzle_sets: Res<Assets<PuzzleSet>>,
+    progress: Res<PuzzleProgress>,
+    state: Res<PuzzlesMenuState>,
+    previous: Query<Entity, With<PuzzlesMenu>>,
+) {
+    for entity in previous.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if !state.open {
+        return;
+    }
+    let puzzles = puzzle_sets
+        .get(&assets.puzzles)
+        .map_or(&[][..], |set| &set.puzzles[..]);
+
+    commands
+        .spawn((
+            NodeBundle {
+                z_index: ZIndex::Global(10),
+                ..screen(Color::WHITE.with_a(0.9))
+            },
+            PuzzlesMenu,
+        ))
+        .with_children(|parent| {
+            title(parent, &fonts, locale.t("puzzles-title"));
+            label(
+                parent,
+                &fonts,
+                locale.t_args(
+                    "puzzles-solved",
+                    &[
+                        (
+                            "solved",
+                            puzzles
+                                .iter()
+                                .filter(|puzzle| progress.is_solved(&puzzle.id))
+                                .count()
+                                .into(),
+                        ),
+                        ("count", puzzles.len().into()),
+                    ],
+                ),
+            );
+
+            for (idx, puzzle) in puzzles.iter().enumerate() {
+                row(parent, |parent| {
+                    label(parent, &fonts, format!("{}.", idx + 1));
+                    let rules = locale.t(puzzle.position.rules.label_id());
+                    button(
+                        parent,
+                        &fonts,
+                        locale.t_args(
+                            "puzzles-win-in",
+                            &[("moves", puzzle.moves.into()), ("rules", rules.into())],
+                        ),
+                        MenuButton::StartPuzzle(idx),
+                    );
+                    if progress.is_solved(&puzzle.id) {
+                        label(parent, &fonts, locale.t("puzzles-done"));
+                    }
+                });
+            }
+
+            button(
+                parent,
+                &fonts,
+                locale.t("settings-back"),
+                MenuButton::ClosePuzzles,
+            );
+        });
+}
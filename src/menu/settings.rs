@@ -0,0 +1,187 @@
+use bevy::prelude::*;
+
+use crate::{
+    loading::FontAssets,
+    locale::Locale,
+    settings::{KeyAction, Settings},
+};
+
+use super::{
+    widgets::{button, label, row, screen, title},
+    MenuButton,
+};
+
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct SettingsMenu;
+
+#[derive(Debug, Default, Resource)]
+pub struct SettingsMenuState {
+    pub open: bool,
+    /// Action waiting for a key press to be bound to
+    pub rebinding: Option<KeyAction>,
+}
+
+pub fn settings_menu_outdated(settings: Res<Settings>, state: Res<SettingsMenuState>) -> bool {
+    state.is_changed() || (state.open && settings.is_changed())
+}
+
+/// Settings overlay, shown on top of the main or pause menu
+pub fn spawn_settings_menu(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Locale,
+    settings: Res<Settings>,
+    state: Res<SettingsMenuState>,
+    previous: Query<Entity, With<SettingsMenu>>,
+) {
+    for entity in previous.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if !state.open {
+        return;
+    }
+    let on_off = |on: bool| locale.t(if on { "settings-on" } else { "settings-off" });
+
+    commands
+        .spawn((
+            NodeBundle {
+                z_index: ZIndex::Global(10),
+                ..screen(Color::WHITE.with_a(0.9))
+            },
+            SettingsMenu,
+        ))
+        .with_children(|parent| {
+            title(parent, &fonts, locale.t("settings-title"));
+
+            row(parent, |parent| {
+                label(parent, &fonts, locale.t("settings-volume"));
+                button(parent, &fonts, "<", MenuButton::VolumeDown);
+                label(parent, &fonts, format!("{:.0}%", settings.volume * 100.));
+                button(parent, &fonts, ">", MenuButton::VolumeUp);
+                let text = if settings.muted {
+                    "settings-unmute"
+                } else {
+                    "settings-mute"
+                };
+                button(parent, &fonts, locale.t(text), MenuButton::ToggleMute);
+            });
+
+            row(parent, |parent| {
+                label(parent, &fonts, locale.t("settings-music"));
+                button(parent, &fonts, "<", MenuButton::MusicVolumeDown);
+                label(
+                    parent,
+                    &fonts,
+                    format!("{:.0}%", settings.music_volume * 100.),
+                );
+                button(parent, &fonts, ">", MenuButton::MusicVolumeUp);
+            });
+
+            row(parent, |parent| {
+                label(parent, &fonts, locale.t("settings-fullscreen"));
+                button(
+                    parent,
+                    &fonts,
+                    on_off(settings.fullscreen),
+                    MenuButton::ToggleFullscreen,
+                );
+            });
+
+            row(parent, |parent| {
+                label(parent, &fonts, locale.t("settings-resolution"));
+                let (width, height) = settings.resolution;
+                button(
+                    parent,
+                    &fonts,
+                    format!("{width}x{height}"),
+                    MenuButton::CycleResolution,
+                );
+            });
+
+            row(parent, |parent| {
+                label(parent, &fonts, locale.t("settings-animation-speed"));
+                button(parent, &fonts, "<", MenuButton::AnimationSpeedDown);
+                label(parent, &fonts, format!("{:.2}x", settings.animation_speed));
+                button(parent, &fonts, ">", MenuButton::AnimationSpeedUp);
+            });
+
+            row(parent, |parent| {
+                label(parent, &fonts, locale.t("settings-colors"));
+                button(
+                    parent,
+                    &fonts,
+                    locale.t(settings.palette.label_id()),
+                    MenuButton::CyclePalette,
+                );
+            });
+
+            row(parent, |parent| {
+                label(parent, &fonts, locale.t("settings-piece-markers"));
+                button(
+                    parent,
+                    &fonts,
+                    on_off(settings.piece_markers),
+                    MenuButton::TogglePieceMarkers,
+                );
+            });
+
+            row(parent, |parent| {
+                label(parent, &fonts, locale.t("settings-language"));
+                button(
+                    parent,
+                    &fonts,
+                    settings.language.label(),
+                    MenuButton::CycleLanguage,
+                );
+            });
+
+            let key = |action| {
+                if state.rebinding == Some(action) {
+                    locale.t("settings-press-key")
+                } else {
+                    format!("{:?}", settings.keys.get(action))
+                }
+            };
+            for action in KeyAction::ALL
+                .into_iter()
+                .filter(|action| !action.is_alternative())
+            {
+                row(parent, |parent| {
+                    label(parent, &fonts, locale.t(action.label_id()));
+                    button(parent, &fonts, key(action), MenuButton::Rebind(action));
+                    if let Some(alternative) = action.alternative() {
+                        button(
+                            parent,
+                            &fonts,
+                            key(alternative),
+                            MenuButton::Rebind(alternative),
+                        );
+                    }
+                });
+            }
+
+            button(
+                parent,
+                &fonts,
+                locale.t("settings-back"),
+                MenuButton::CloseSettings,
+            );
+        });
+}
+
+/// Bind the next key press to the action picked in the settings menu
+pub fn capture_key(
+    keys: Res<Input<KeyCode>>,
+    mut state: ResMut<SettingsMenuState>,
+    mut settings: ResMut<Settings>,
+) {
+    let Some(action) = state.rebinding else {
+        return;
+    };
+    let Some(key) = keys.get_just_pressed().next() else {
+        return;
+    };
+    settings.keys.set(action, *key);
+    state.rebinding = None;
+}
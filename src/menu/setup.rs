@@ -0,0 +1,237 @@
+use bevy::{prelude::*, window::ReceivedCharacter};
+
+use crate::{
+    config::{GameConfig, Rules, SeriesLength, TimeControl},
+    grid::MapSettings,
+    loading::FontAssets,
+    locale::Locale,
+    profiles::Profiles,
+    settings::Settings,
+};
+
+use super::{
+    widgets::{button, colored_button, label, row, screen, title},
+    MenuButton, NameFocus,
+};
+
+const MAX_NAME_LENGTH: usize = 16;
+
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct SetupMenu;
+
+pub fn setup_menu_outdated(
+    config: Res<GameConfig>,
+    settings: Res<MapSettings>,
+    user_settings: Res<Settings>,
+    focus: Res<NameFocus>,
+    profiles: Res<Profiles>,
+) -> bool {
+    config.is_changed()
+        || settings.is_changed()
+        || user_settings.is_changed()
+        || focus.is_changed()
+        || profiles.is_changed()
+}
+
+/// Game setup screen, rebuilt whenever the configuration changes
+pub fn spawn_setup_menu(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Locale,
+    config: Res<GameConfig>,
+    settings: Res<MapSettings>,
+    user_settings: Res<Settings>,
+    focus: Res<NameFocus>,
+    profiles: Res<Profiles>,
+    previous: Query<Entity, With<SetupMenu>>,
+) {
+    for entity in previous.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    commands
+        .spawn((screen(Color::WHITE.with_a(0.8)), SetupMenu))
+        .with_children(|parent| {
+            title(parent, &fonts, "boop!");
+
+            row(parent, |parent| {
+                label(parent, &fonts, locale.t("menu-board-size"));
+                button(parent, &fonts, "<", MenuButton::RadiusDown);
+                label(parent, &fonts, settings.map_radius.to_string());
+                button(parent, &fonts, ">", MenuButton::RadiusUp);
+            });
+
+            row(parent, |parent| {
+                label(parent, &fonts, locale.t("menu-players"));
+                button(parent, &fonts, "<", MenuButton::PlayersDown);
+                label(parent, &fonts, config.players.len().to_string());
+                button(parent, &fonts, ">", MenuButton::PlayersUp);
+            });
+
+            for (idx, player) in config.players.iter().enumerate() {
+                row(parent, |parent| {
+                    let name = if focus.0 == Some(idx) {
+                        format!("{}|", player.name)
+                    } else {
+                        player.name.clone()
+                    };
+                    button(parent, &fonts, name, MenuButton::EditName(idx));
+                    colored_button(
+                        parent,
+                        &fonts,
+                        "",
+                        player.color,
+                        MenuButton::CycleColor(idx),
+                    );
+                    button(
+                        parent,
+                        &fonts,
+                        locale.t(player.kind.label_id()),
+                        MenuButton::TogglePlayerKind(idx),
+                    );
+                    if !profiles.profiles.is_empty() {
+                        button(
+                            parent,
+                            &fonts,
+                            locale.t("menu-profile"),
+                            MenuButton::CycleProfile(idx),
+                        );
+                    }
+                });
+            }
+
+            row(parent, |parent| {
+                label(parent, &fonts, locale.t("menu-rules"));
+                button(
+                    parent,
+                    &fonts,
+                    locale.t(config.rules.label_id()),
+                    MenuButton::ToggleRules,
+                );
+            });
+            if config.rules == Rules::Graduation {
+                label(
+                    parent,
+                    &fonts,
+                    locale.t_args(
+                        "menu-switch-piece-hint",
+                        &[(
+                            "key",
+                            format!("{:?}", user_settings.keys.switch_piece).into(),
+                        )],
+                    ),
+                );
+            }
+
+            row(parent, |parent| {
+                label(parent, &fonts, locale.t("menu-time"));
+                button(
+                    parent,
+                    &fonts,
+                    time_control_label(&locale, config.time_control),
+                    MenuButton::CycleTimeControl,
+                );
+            });
+
+            row(parent, |parent| {
+                label(parent, &fonts, locale.t("menu-series"));
+                let series = match config.series {
+                    SeriesLength::Single => locale.t("series-single"),
+                    SeriesLength::BestOf(games) => {
+                        locale.t_args("series-best-of", &[("games", games.into())])
+                    }
+                };
+                button(parent, &fonts, series, MenuButton::CycleSeries);
+            });
+
+            button(
+                parent,
+                &fonts,
+                locale.t("menu-settings"),
+                MenuButton::OpenSettings,
+            );
+            button(
+                parent,
+                &fonts,
+                locale.t("menu-profiles"),
+                MenuButton::OpenProfiles,
+            );
+            button(
+                parent,
+                &fonts,
+                locale.t("menu-leaderboard"),
+                MenuButton::OpenLeaderboard,
+            );
+            row(parent, |parent| {
+                button(parent, &fonts, locale.t("menu-start"), MenuButton::Start);
+                button(
+                    parent,
+                    &fonts,
+                    locale.t("menu-tutorial"),
+                    MenuButton::Tutorial,
+                );
+                button(
+                    parent,
+                    &fonts,
+                    locale.t("menu-puzzles"),
+                    MenuButton::OpenPuzzles,
+                );
+                button(
+                    parent,
+                    &fonts,
+                    locale.t("menu-analysis"),
+                    MenuButton::Analysis,
+                );
+            });
+        });
+}
+
+fn time_control_label(locale: &Locale, time_control: TimeControl) -> String {
+    match time_control {
+        TimeControl::Unlimited => locale.t("time-unlimited"),
+        TimeControl::Clock { minutes, increment } => locale.t_args(
+            "time-clock",
+            &[("minutes", minutes.into()), ("increment", increment.into())],
+        ),
+        TimeControl::PerMove { seconds } => {
+            locale.t_args("time-per-move", &[("seconds", seconds.into())])
+        }
+    }
+}
+
+/// Type into the focused player name
+pub fn edit_name(
+    keys: Res<Input<KeyCode>>,
+    mut chars: EventReader<ReceivedCharacter>,
+    mut focus: ResMut<NameFocus>,
+    mut config: ResMut<GameConfig>,
+) {
+    let Some(idx) = focus.0 else {
+        chars.clear();
+        return;
+    };
+
+    if keys.any_just_pressed([KeyCode::Return, KeyCode::Escape]) {
+        focus.0 = None;
+        chars.clear();
+        return;
+    }
+
+    let typed = chars
+        .iter()
+        .map(|ev| ev.char)
+        .filter(|char| !char.is_control())
+        .collect::<String>();
+    let delete = keys.just_pressed(KeyCode::Back);
+    if typed.is_empty() && !delete {
+        return;
+    }
+
+    let name = &mut config.players[idx].name;
+    if delete {
+        name.pop();
+    }
+    let room = MAX_NAME_LENGTH.saturating_sub(name.chars().count());
+    name.extend(typed.chars().take(room));
+}
@@ -0,0 +1,126 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+
+use crate::loading::FontAssets;
+
+use super::MenuButton;
+
+/// Full-screen, semi-transparent container for menu screens
+pub fn screen(color: Color) -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            size: Size::all(Val::Percent(100.)),
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            padding: UiRect::all(Val::Px(20.)),
+            gap: Size::all(Val::Px(10.)),
+            ..default()
+        },
+        background_color: BackgroundColor(color),
+        focus_policy: FocusPolicy::Block,
+        ..default()
+    }
+}
+
+pub fn title(parent: &mut ChildBuilder, fonts: &FontAssets, text: impl Into<String>) {
+    parent.spawn((TextBundle::from_section(
+        text,
+        TextStyle {
+            font: fonts.fira_sans.clone(),
+            font_size: 48.0,
+            color: Color::BLACK,
+        },
+    ),));
+}
+
+pub fn label(parent: &mut ChildBuilder, fonts: &FontAssets, text: impl Into<String>) {
+    parent.spawn((TextBundle::from_section(
+        text,
+        TextStyle {
+            font: fonts.fira_sans.clone(),
+            font_size: 18.0,
+            color: Color::BLACK,
+        },
+    ),));
+}
+
+/// Horizontal group of widgets
+pub fn row(parent: &mut ChildBuilder, children: impl FnOnce(&mut ChildBuilder)) {
+    parent
+        .spawn((NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                gap: Size::all(Val::Px(10.)),
+                ..default()
+            },
+            ..default()
+        },))
+        .with_children(children);
+}
+
+pub fn button(
+    parent: &mut ChildBuilder,
+    fonts: &FontAssets,
+    text: impl Into<String>,
+    action: MenuButton,
+) {
+    colored_button(parent, fonts, text, Color::WHITE.with_a(0.5), action);
+}
+
+pub fn colored_button(
+    parent: &mut ChildBuilder,
+    fonts: &FontAssets,
+    text: impl Into<String>,
+    color: Color,
+    action: MenuButton,
+) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    padding: UiRect::all(Val::Px(5.)),
+                    min_size: Size::width(Val::Px(30.)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(color),
+                ..default()
+            },
+            ButtonColor(color),
+            action,
+        ))
+        .with_children(|button| {
+            let mut text = TextBundle::from_section(
+                text,
+                TextStyle {
+                    font: fonts.fira_sans.clone(),
+                    font_size: 18.0,
+                    color: Color::DARK_GRAY,
+                },
+            );
+            text.focus_policy = FocusPolicy::Pass;
+            button.spawn((text,));
+        });
+}
+
+/// Background color of a button when it is not hovered
+#[derive(Debug, Clone, Copy, Component)]
+pub struct ButtonColor(pub Color);
+
+pub fn highlight_buttons(
+    mut buttons: Query<
+        (&Interaction, &ButtonColor, &mut BackgroundColor),
+        (Changed<Interaction>, With<MenuButton>),
+    >,
+) {
+    for (interaction, ButtonColor(color), mut background) in buttons.iter_mut() {
+        *background = match interaction {
+            Interaction::Clicked | Interaction::Hovered => {
+                BackgroundColor(color.with_a((color.a() + 0.3).min(1.)))
+            }
+            Interaction::None => BackgroundColor(*color),
+        };
+    }
+}
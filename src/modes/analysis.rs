@@ -0,0 +1,802 @@
+//! Analysis board: set up any position, play moves from it, and see how it
+//! looks for the player to move and which moves the search likes best.
+
+use bevy::{
+    prelude::{shape::Torus, *},
+    tasks::AsyncComputeTaskPool,
+    ui::FocusPolicy,
+};
+use hexx::Hex;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+use tracing::instrument;
+
+use crate::{
+    cats::{Cat, Meowple},
+    config::{GameConfig, PlayerConfig, PlayerKind},
+    events::{GridCellClicked, NewCat, ResetGameEvent},
+    grid::{Map, MapSettings},
+    history,
+    loading::FontAssets,
+    locale::Locale,
+    players::{PlayerId, Players},
+    rules::{Game, SavedGame},
+    search::{self, WIN_SCORE},
+    settings::Settings,
+    GameState,
+};
+
+use super::{GameMode, StartMode, StartPosition};
+
+/// Deepest the search looks, in moves
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_DEPTH: u32 = 3;
+/// Searches run on the main thread in the browser, so they stay shallow there
+#[cfg(target_arch = "wasm32")]
+const MAX_DEPTH: u32 = 2;
+/// Best moves shown on the board and in the panel
+const SUGGESTIONS: usize = 3;
+/// Most kittens or cats a player can get in hand
+const MAX_IN_HAND: u8 = 8;
+/// Score at which the evaluation bar is three quarters full
+const BAR_SCALE: f32 = 50.;
+
+pub struct AnalysisPlugin;
+
+impl Plugin for AnalysisPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AnalysisBoard>();
+        app.init_resource::<BoardEditor>();
+        app.init_resource::<Evaluation>();
+        app.add_event::<StartAnalysis>();
+
+        app.add_system(start_analysis.run_if(on_event::<StartAnalysis>()));
+        app.add_system(
+            reset_board
+                .run_if(on_event::<ResetGameEvent>())
+                .run_if(in_analysis),
+        );
+        app.add_system(
+            follow_moves
+                .run_if(on_event::<NewCat>())
+                .run_if(in_analysis)
+                .in_base_set(CoreSet::PostUpdate),
+        );
+        app.add_systems(
+            (
+                edit_board.run_if(on_event::<GridCellClicked>()),
+                analysis_buttons,
+                update_evaluation,
+                show_suggestions.after(update_evaluation),
+            )
+                .distributive_run_if(in_analysis),
+        );
+        app.add_system(
+            show_panel
+                .after(update_evaluation)
+                .run_if(resource_exists::<FontAssets>())
+                .run_if(panel_outdated),
+        );
+        app.add_system(leave_analysis.in_schedule(OnEnter(GameState::Menu)));
+    }
+}
+
+fn in_analysis(mode: Res<GameMode>) -> bool {
+    *mode == GameMode::Analysis
+}
+
+/// Open the analysis board, with the position of the game being played or
+/// an empty board for the players set up in the main menu
+#[derive(Debug)]
+pub struct StartAnalysis {
+    pub current_game: bool,
+}
+
+/// Position on the analysis board, followed with the headless rules
+#[derive(Debug, Default, Resource)]
+struct AnalysisBoard {
+    game: Option<Game>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Tool {
+    /// Clicks make moves like in a game
+    #[default]
+    Play,
+    /// Clicks put a piece of this kind on the cell
+    Put(Cat),
+    Remove,
+}
+
+#[derive(Debug, Default, Resource)]
+pub struct BoardEditor {
+    tool: Tool,
+    /// Player the pieces put on the board belong to
+    owner: usize,
+}
+
+/// Whether clicks on cells make moves, instead of editing the board
+pub fn clicks_place_cats(editor: Res<BoardEditor>) -> bool {
+    editor.tool == Tool::Play
+}
+
+/// Deepest search done so far, and the one running in the background
+#[derive(Default, Resource)]
+struct Evaluation {
+    result: Option<(u32, search::Analysis)>,
+    running: Option<Search>,
+}
+
+/// Search running in the background, cancelled when dropped
+struct Search {
+    depth: u32,
+    cancel: Arc<AtomicBool>,
+    result: Arc<Mutex<Option<search::Analysis>>>,
+}
+
+impl Search {
+    fn start(game: Game, depth: u32) -> Self {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let result = Arc::new(Mutex::new(None));
+        let (task_cancel, task_result) = (cancel.clone(), result.clone());
+        // the result is handed over instead of polling the task, which the
+        // single threaded task pool on wasm doesn't support
+        AsyncComputeTaskPool::get()
+            .spawn(async move {
+                let analysis = search::analyse(&game, depth, &task_cancel);
+                if let (Some(analysis), Ok(mut result)) = (analysis, task_result.lock()) {
+                    *result = Some(analysis);
+                }
+            })
+            .detach();
+        Self {
+            depth,
+            cancel,
+            result,
+        }
+    }
+
+    fn finished(&self) -> Option<search::Analysis> {
+        self.result.lock().ok()?.take()
+    }
+}
+
+impl Drop for Search {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+#[instrument(level = "debug", skip_all)]
+fn start_analysis(
+    config: Res<GameConfig>,
+    map_settings: Res<MapSettings>,
+    map: Res<Map>,
+    players: Res<Players>,
+    pieces: Query<(&Cat, &PlayerId), With<Meowple>>,
+    mut editor: ResMut<BoardEditor>,
+    mut events: EventReader<StartAnalysis>,
+    mut starts: EventWriter<StartMode>,
+) {
+    let Some(start) = events.iter().last() else {
+        return;
+    };
+    info!(current_game = start.current_game, "Starting analysis");
+
+    let position = if start.current_game {
+        let pieces = history::position(&map, |entity| {
+            pieces.get(entity).ok().map(|(cat, owner)| (*cat, *owner))
+        });
+        SavedGame {
+            rules: config.rules,
+            radius: map_settings.map_radius,
+            inventories: players
+                .players
+                .iter()
+                .map(|player| (player.inventory.kittens(), player.inventory.cats()))
+                .collect(),
+            current: players.current().id.0,
+            pieces: pieces
+                .into_iter()
+                .map(|(hex, cat, owner)| ((hex.x, hex.y), cat, owner.0))
+                .collect(),
+        }
+    } else {
+        SavedGame::from(&Game::new(
+            config.players.len(),
+            config.rules,
+            map_settings.map_radius,
+        ))
+    };
+    *editor = BoardEditor::default();
+    starts.send(StartMode {
+        mode: GameMode::Analysis,
+        config: GameConfig {
+            // nobody plays by themselves on the analysis board
+            players: config
+                .players
+                .iter()
+                .map(|player| PlayerConfig {
+                    kind: PlayerKind::Human,
+                    ..player.clone()
+                })
+                .collect(),
+            ..default()
+        },
+        position,
+    });
+}
+
+/// (Re)starts begin from the position the board was last edited to
+fn reset_board(start_position: Res<StartPosition>, mut board: ResMut<AnalysisBoard>) {
+    board.game = start_position.0.clone().map(Game::from);
+}
+
+/// Moves played on the board are made on the headless game too
+#[instrument(level = "debug", skip_all)]
+fn follow_moves(mut board: ResMut<AnalysisBoard>, mut new_cats: EventReader<NewCat>) {
+    let Some(game) = board.game.as_mut() else {
+        return;
+    };
+    for NewCat { cat, position, .. } in new_cats.iter() {
+        if let Err(error) = game.place(*position, *cat) {
+            error!(
+                ?error,
+                ?position,
+                "Analysis position out of sync with the board"
+            );
+        }
+    }
+}
+
+/// Change the position and start over from it
+fn edit(
+    board: &AnalysisBoard,
+    start_position: &mut StartPosition,
+    reset: &mut EventWriter<ResetGameEvent>,
+    next_state: &mut NextState<GameState>,
+    change: impl FnOnce(&mut SavedGame),
+) {
+    let Some(game) = &board.game else {
+        return;
+    };
+    let mut position = SavedGame::from(game);
+    change(&mut position);
+    start_position.0 = Some(position);
+    reset.send(ResetGameEvent);
+    next_state.set(GameState::Playing);
+}
+
+#[instrument(level = "debug", skip_all)]
+fn edit_board(
+    board: Res<AnalysisBoard>,
+    editor: Res<BoardEditor>,
+    map: Res<Map>,
+    mut clicks: EventReader<GridCellClicked>,
+    mut start_position: ResMut<StartPosition>,
+    mut reset: EventWriter<ResetGameEvent>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if editor.tool == Tool::Play {
+        clicks.clear();
+        return;
+    }
+    let Some(hex) = clicks
+        .iter()
+        .filter(|click| click.primary)
+        .find_map(|click| map.cell_by_entity(click.cell))
+    else {
+        return;
+    };
+    debug!(?hex, tool = ?editor.tool, "Editing analysis board");
+    edit(
+        &board,
+        &mut start_position,
+        &mut reset,
+        &mut next_state,
+        |position| {
+            position.pieces.retain(|(cell, ..)| *cell != (hex.x, hex.y));
+            if let Tool::Put(cat) = editor.tool {
+                position
+                    .pieces
+                    .push(((hex.x, hex.y), cat, editor.owner as u8));
+            }
+        },
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+enum AnalysisButton {
+    Tool(Tool),
+    Owner(usize),
+    /// One more (or less) of this kind in the player's hand
+    InHand {
+        player: usize,
+        cat: Cat,
+        more: bool,
+    },
+    NextToMove,
+    Clear,
+    Quit,
+}
+
+#[instrument(level = "debug", skip_all)]
+fn analysis_buttons(
+    board: Res<AnalysisBoard>,
+    buttons: Query<(&Interaction, &AnalysisButton), Changed<Interaction>>,
+    mut editor: ResMut<BoardEditor>,
+    mut start_position: ResMut<StartPosition>,
+    mut reset: EventWriter<ResetGameEvent>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        debug!(?button, "Analysis button clicked");
+        let change: Box<dyn Fn(&mut SavedGame)> = match *button {
+            AnalysisButton::Tool(tool) => {
+                editor.tool = tool;
+                continue;
+            }
+            AnalysisButton::Owner(idx) => {
+                editor.owner = idx;
+                continue;
+            }
+            AnalysisButton::InHand { player, cat, more } => Box::new(move |position| {
+                let (kittens, cats) = &mut position.inventories[player];
+                let count = match cat {
+                    Cat::Kitten => kittens,
+                    Cat::Adult => cats,
+                };
+                *count = if more {
+                    (*count + 1).min(MAX_IN_HAND)
+                } else {
+                    count.saturating_sub(1)
+                };
+            }),
+            AnalysisButton::NextToMove => Box::new(|position| {
+                position.current = (position.current + 1) % position.inventories.len() as u8;
+            }),
+            AnalysisButton::Clear => Box::new(|position| position.pieces.clear()),
+            AnalysisButton::Quit => {
+                next_state.set(GameState::Menu);
+                continue;
+            }
+        };
+        edit(
+            &board,
+            &mut start_position,
+            &mut reset,
+            &mut next_state,
+            change,
+        );
+    }
+}
+
+/// Search the position one move deeper at a time, starting over whenever it
+/// changes
+#[instrument(level = "debug", skip_all)]
+fn update_evaluation(board: Res<AnalysisBoard>, mut evaluation: ResMut<Evaluation>) {
+    if board.is_changed() {
+        // dropping the running search cancels it
+        *evaluation = Evaluation::default();
+    }
+    // checking on the search doesn't change anything shown
+    if let Some(search) = &evaluation.bypass_change_detection().running {
+        let Some(analysis) = search.finished() else {
+            return;
+        };
+        let depth = search.depth;
+        debug!(depth, score = analysis.score, "Analysis done");
+        evaluation.running = None;
+        evaluation.result = Some((depth, analysis));
+    }
+
+    let (depth, decided) = match &evaluation.result {
+        Some((depth, analysis)) => (depth + 1, analysis.moves.is_empty()),
+        None => (1, false),
+    };
+    if depth > MAX_DEPTH || decided {
+        return;
+    }
+    let Some(game) = board.game.clone() else {
+        return;
+    };
+    evaluation.running = Some(Search::start(game, depth));
+}
+
+/// Ring around each cell of the best moves, smaller for the worse ones
+#[derive(Debug, Default, Component)]
+struct SuggestionRing;
+
+#[instrument(level = "debug", skip_all)]
+fn show_suggestions(
+    mut commands: Commands,
+    evaluation: Res<Evaluation>,
+    map: Res<Map>,
+    settings: Res<MapSettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    rings: Query<Entity, With<SuggestionRing>>,
+) {
+    if !evaluation.is_changed() {
+        return;
+    }
+    for ring in rings.iter() {
+        commands.entity(ring).despawn_recursive();
+    }
+    let Some((_, analysis)) = &evaluation.result else {
+        return;
+    };
+
+    let material = materials.add(StandardMaterial {
+        base_color: Color::LIME_GREEN,
+        unlit: true,
+        ..default()
+    });
+    for (rank, hex) in best_cells(analysis).into_iter().enumerate() {
+        let Some(cell) = map.cell_by_hex(hex) else {
+            continue;
+        };
+        let mesh = meshes.add(
+            Torus {
+                radius: settings.cell_size.x * (0.9 - 0.2 * rank as f32),
+                ring_radius: 0.12,
+                ..default()
+            }
+            .into(),
+        );
+        commands.entity(cell).with_children(|parent| {
+            parent.spawn((
+                PbrBundle {
+                    mesh,
+                    material: material.clone(),
+                    // on top of the platform
+                    transform: Transform::from_xyz(0., settings.column_height / 2., 0.),
+                    ..default()
+                },
+                Name::from("Suggested move"),
+                SuggestionRing,
+            ));
+        });
+    }
+}
+
+/// Cells of the best moves, each once
+fn best_cells(analysis: &search::Analysis) -> Vec<Hex> {
+    let mut cells = Vec::new();
+    for ((hex, _), _) in &analysis.moves {
+        if cells.len() == SUGGESTIONS {
+            break;
+        }
+        if !cells.contains(hex) {
+            cells.push(*hex);
+        }
+    }
+    cells
+}
+
+/// Back to normal clicks, and nothing left of the analysis on the board
+fn leave_analysis(
+    mut commands: Commands,
+    mut board: ResMut<AnalysisBoard>,
+    mut editor: ResMut<BoardEditor>,
+    mut evaluation: ResMut<Evaluation>,
+    rings: Query<Entity, With<SuggestionRing>>,
+) {
+    *board = AnalysisBoard::default();
+    *editor = BoardEditor::default();
+    *evaluation = Evaluation::default();
+    for ring in rings.iter() {
+        commands.entity(ring).despawn_recursive();
+    }
+}
+
+#[derive(Debug, Default, Component)]
+struct AnalysisPanel;
+
+fn panel_outdated(
+    mode: Res<GameMode>,
+    board: Res<AnalysisBoard>,
+    editor: Res<BoardEditor>,
+    evaluation: Res<Evaluation>,
+    players: Res<Players>,
+    settings: Res<Settings>,
+) -> bool {
+    mode.is_changed()
+        || board.is_changed()
+        || editor.is_changed()
+        || evaluation.is_changed()
+        || players.is_changed()
+        || settings.is_changed()
+}
+
+/// Score of a move or position for the player making it
+fn score_text(locale: &Locale, score: i32) -> String {
+    if score >= WIN_SCORE {
+        locale.t("analysis-winning")
+    } else if score <= -WIN_SCORE {
+        locale.t("analysis-losing")
+    } else {
+        format!("{score:+}")
+    }
+}
+
+/// Evaluation bar, best moves and tools to edit the position on the right
+#[instrument(level = "debug", skip_all)]
+fn show_panel(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Locale,
+    mode: Res<GameMode>,
+    board: Res<AnalysisBoard>,
+    editor: Res<BoardEditor>,
+    evaluation: Res<Evaluation>,
+    players: Res<Players>,
+    previous: Query<Entity, With<AnalysisPanel>>,
+) {
+    for entity in previous.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let Some(game) = board.game.as_ref().filter(|_| *mode == GameMode::Analysis) else {
+        return;
+    };
+    let to_move = players.by_id(game.current());
+
+    let text_style = |size| TextStyle {
+        font: fonts.fira_sans.clone(),
+        font_size: size,
+        color: Color::BLACK,
+    };
+    let text = |parent: &mut ChildBuilder, text: String, size| {
+        parent.spawn((TextBundle::from_section(text, text_style(size)),));
+    };
+    let button = |parent: &mut ChildBuilder, text: String, color: Color, action| {
+        parent
+            .spawn((
+                ButtonBundle {
+                    style: Style {
+                        padding: UiRect::all(Val::Px(4.)),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(color),
+                    ..default()
+                },
+                action,
+            ))
+            .with_children(|parent| {
+                let mut text = TextBundle::from_section(text, text_style(16.));
+                text.focus_policy = FocusPolicy::Pass;
+                parent.spawn((text,));
+            });
+    };
+    let row = |parent: &mut ChildBuilder, children: &dyn Fn(&mut ChildBuilder)| {
+        parent
+            .spawn((NodeBundle {
+                style: Style {
+                    align_items: AlignItems::Center,
+                    gap: Size::all(Val::Px(6.)),
+                    ..default()
+                },
+                ..default()
+            },))
+            .with_children(|parent| children(parent));
+    };
+    // the chosen one of a group of buttons stands out
+    let chosen = |color: Color, is_chosen| color.with_a(if is_chosen { 0.9 } else { 0.3 });
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        left: Val::Px(10.),
+                        bottom: Val::Px(10.),
+                        ..default()
+                    },
+                    size: Size::width(Val::Px(340.)),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.)),
+                    gap: Size::all(Val::Px(6.)),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::WHITE.with_a(0.8)),
+                ..default()
+            },
+            Name::from("Analysis"),
+            AnalysisPanel,
+        ))
+        .with_children(|parent| {
+            text(parent, locale.t("analysis-title"), 24.);
+
+            let (score, depth) = match &evaluation.result {
+                Some((depth, analysis)) => (Some(analysis.score), *depth),
+                None => (None, 0),
+            };
+            if let Some(player) = to_move {
+                text(
+                    parent,
+                    locale.t_args(
+                        "analysis-to-move",
+                        &[
+                            ("player", player.name.clone().into()),
+                            (
+                                "score",
+                                score
+                                    .map_or_else(
+                                        || "…".to_string(),
+                                        |score| score_text(&locale, score),
+                                    )
+                                    .into(),
+                            ),
+                        ],
+                    ),
+                    18.,
+                );
+                evaluation_bar(parent, &players, player.id, score.unwrap_or_default());
+            }
+            text(
+                parent,
+                match &evaluation.running {
+                    Some(search) => {
+                        locale.t_args("analysis-thinking", &[("depth", search.depth.into())])
+                    }
+                    None => locale.t_args("analysis-depth", &[("depth", depth.into())]),
+                },
+                14.,
+            );
+            if let Some((_, analysis)) = &evaluation.result {
+                for (rank, ((hex, cat), score)) in
+                    analysis.moves.iter().take(SUGGESTIONS).enumerate()
+                {
+                    let piece = match cat {
+                        Cat::Kitten => locale.t("analysis-kitten"),
+                        Cat::Adult => locale.t("analysis-cat"),
+                    };
+                    text(
+                        parent,
+                        locale.t_args(
+                            "analysis-move",
+                            &[
+                                ("rank", (rank + 1).into()),
+                                ("piece", piece.into()),
+                                ("cell", format!("{},{}", hex.x, hex.y).into()),
+                                ("score", score_text(&locale, *score).into()),
+                            ],
+                        ),
+                        16.,
+                    );
+                }
+            }
+
+            row(parent, &|parent| {
+                for (tool, text_id) in [
+                    (Tool::Play, "analysis-tool-play"),
+                    (Tool::Put(Cat::Kitten), "analysis-tool-kitten"),
+                    (Tool::Put(Cat::Adult), "analysis-tool-cat"),
+                    (Tool::Remove, "analysis-tool-remove"),
+                ] {
+                    button(
+                        parent,
+                        locale.t(text_id),
+                        chosen(Color::WHITE, editor.tool == tool),
+                        AnalysisButton::Tool(tool),
+                    );
+                }
+            });
+            if matches!(editor.tool, Tool::Put(_)) {
+                row(parent, &|parent| {
+                    text(parent, locale.t("analysis-owner"), 16.);
+                    for (idx, player) in players.players.iter().enumerate() {
+                        button(
+                            parent,
+                            player.name.clone(),
+                            chosen(player.color, editor.owner == idx),
+                            AnalysisButton::Owner(idx),
+                        );
+                    }
+                });
+            }
+
+            let position = SavedGame::from(game);
+            for (idx, player) in players.players.iter().enumerate() {
+                let Some((kittens, cats)) = position.inventories.get(idx) else {
+                    continue;
+                };
+                row(parent, &|parent| {
+                    text(parent, player.name.clone(), 16.);
+                    for (cat, count, text_id) in [
+                        (Cat::Kitten, *kittens, "kittens-count"),
+                        (Cat::Adult, *cats, "cats-count"),
+                    ] {
+                        let in_hand = |more| AnalysisButton::InHand {
+                            player: idx,
+                            cat,
+                            more,
+                        };
+                        button(parent, "-".into(), Color::WHITE.with_a(0.5), in_hand(false));
+                        text(
+                            parent,
+                            locale.t_args(text_id, &[("count", count.into())]),
+                            16.,
+                        );
+                        button(parent, "+".into(), Color::WHITE.with_a(0.5), in_hand(true));
+                    }
+                });
+            }
+
+            row(parent, &|parent| {
+                if let Some(player) = to_move {
+                    button(
+                        parent,
+                        locale.t_args(
+                            "analysis-next-to-move",
+                            &[("player", player.name.clone().into())],
+                        ),
+                        player.color.with_a(0.6),
+                        AnalysisButton::NextToMove,
+                    );
+                }
+                button(
+                    parent,
+                    locale.t("analysis-clear"),
+                    Color::WHITE.with_a(0.5),
+                    AnalysisButton::Clear,
+                );
+                button(
+                    parent,
+                    locale.t("analysis-quit"),
+                    Color::WHITE.with_a(0.5),
+                    AnalysisButton::Quit,
+                );
+            });
+        });
+}
+
+/// Share of the player to move in the player's color, the rest in the color
+/// of the other player (or gray with more of them)
+fn evaluation_bar(parent: &mut ChildBuilder, players: &Players, to_move: PlayerId, score: i32) {
+    let share = if score.abs() >= WIN_SCORE {
+        if score > 0 {
+            1.
+        } else {
+            0.
+        }
+    } else {
+        // half full when even, three quarters at `BAR_SCALE`
+        0.5 + 0.5 * (score as f32 / BAR_SCALE * 0.55).tanh()
+    };
+    let own = players
+        .by_id(to_move)
+        .map_or(Color::GRAY, |player| player.color);
+    let others = match &players.players[..] {
+        [first, second] if first.id == to_move => second.color,
+        [first, _] => first.color,
+        _ => Color::GRAY,
+    };
+
+    parent
+        .spawn((NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Px(16.)),
+                ..default()
+            },
+            ..default()
+        },))
+        .with_children(|parent| {
+            for (width, color) in [(share, own), (1. - share, others)] {
+                parent.spawn((NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(width * 100.), Val::Percent(100.)),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(color),
+                    ..default()
+                },));
+            }
+        });
+}
@@ -0,0 +1,274 @@
+//! Ways to play besides a normal game, which start from prepared positions
+//! with their own setup and don't count for profiles or ratings.
+
+use bevy::{
+    prelude::{shape::Torus, *},
+    ui::FocusPolicy,
+};
+use bevy_mod_picking::prelude::RaycastPickTarget;
+use hexx::Hex;
+use tracing::instrument;
+
+use crate::{
+    config::GameConfig,
+    events::{BoardReady, ResetGameEvent},
+    grid::{GridCell, Hovered, MapSettings, Platform},
+    loading::FontAssets,
+    rules::SavedGame,
+    GameState,
+};
+
+pub mod analysis;
+pub mod puzzle;
+pub mod tutorial;
+
+pub struct ModesPlugin;
+
+impl Plugin for ModesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameMode>();
+        app.register_type::<GameMode>();
+        app.init_resource::<StartPosition>();
+        app.init_resource::<AllowedCells>();
+        app.init_resource::<StashedSetup>();
+        app.add_event::<StartMode>();
+
+        app.add_system(start_mode.run_if(on_event::<StartMode>()));
+        app.add_system(restore_setup.in_schedule(OnEnter(GameState::Menu)));
+        app.add_system(show_allowed_cells.in_base_set(CoreSet::PostUpdate));
+
+        app.add_plugin(tutorial::TutorialPlugin);
+        app.add_plugin(puzzle::PuzzlePlugin);
+        app.add_plugin(analysis::AnalysisPlugin);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Resource, Reflect)]
+#[reflect(Resource)]
+pub enum GameMode {
+    /// Set up in the main menu
+    #[default]
+    Normal,
+    Tutorial,
+    Puzzle,
+    /// Free setup with the search's opinion on the position
+    Analysis,
+}
+
+/// Whether the current game is a normal one, which counts for the match,
+/// profiles and ratings
+pub fn normal_game(mode: Res<GameMode>) -> bool {
+    *mode == GameMode::Normal
+}
+
+/// Pieces, inventories and the first player the next game starts with
+/// instead of an empty board
+#[derive(Debug, Default, Resource)]
+pub struct StartPosition(pub Option<SavedGame>);
+
+/// Cells moves can be made on, all of them when `None`
+#[derive(Debug, Default, Resource)]
+pub struct AllowedCells(pub Option<Vec<Hex>>);
+
+impl AllowedCells {
+    pub fn allows(&self, hex: Hex) -> bool {
+        self.0.as_ref().map_or(true, |cells| cells.contains(&hex))
+    }
+}
+
+/// Setup of the main menu, put back when returning to it
+#[derive(Debug, Default, Resource)]
+struct StashedSetup(Option<(GameConfig, u32)>);
+
+/// (Re)start a game in `mode`, from `position` with its rules and board size
+#[derive(Debug)]
+pub struct StartMode {
+    pub mode: GameMode,
+    pub config: GameConfig,
+    pub position: SavedGame,
+}
+
+#[instrument(level = "debug", skip_all)]
+fn start_mode(
+    mut starts: EventReader<StartMode>,
+    mut mode: ResMut<GameMode>,
+    mut config: ResMut<GameConfig>,
+    mut map_settings: ResMut<MapSettings>,
+    mut start_position: ResMut<StartPosition>,
+    mut stash: ResMut<StashedSetup>,
+    mut reset: EventWriter<ResetGameEvent>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(start) = starts.iter().last() else {
+        return;
+    };
+    info!(mode = ?start.mode, "Starting game");
+    if stash.0.is_none() {
+        stash.0 = Some((config.clone(), map_settings.map_radius));
+    }
+    *mode = start.mode;
+    *config = GameConfig {
+        rules: start.position.rules,
+        ..start.config.clone()
+    };
+    map_settings.map_radius = start.position.radius;
+    start_position.0 = Some(start.position.clone());
+    reset.send(ResetGameEvent);
+    next_state.set(GameState::Playing);
+}
+
+/// Back in the main menu, the next game is a normal one again
+fn restore_setup(
+    mut mode: ResMut<GameMode>,
+    mut config: ResMut<GameConfig>,
+    mut map_settings: ResMut<MapSettings>,
+    mut start_position: ResMut<StartPosition>,
+    mut allowed: ResMut<AllowedCells>,
+    mut stash: ResMut<StashedSetup>,
+) {
+    *mode = GameMode::Normal;
+    start_position.0 = None;
+    allowed.0 = None;
+    if let Some((stashed_config, radius)) = stash.0.take() {
+        *config = stashed_config;
+        map_settings.map_radius = radius;
+    }
+}
+
+/// Ring around each cell a move can be made on, while only some are allowed
+#[derive(Debug, Default, Component)]
+struct AllowedRing;
+
+/// Only allowed cells can be picked, and they get a ring while not all of
+/// them are allowed
+#[instrument(level = "debug", skip_all)]
+fn show_allowed_cells(
+    mut commands: Commands,
+    allowed: Res<AllowedCells>,
+    settings: Res<MapSettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut ready: EventReader<BoardReady>,
+    cells: Query<(Entity, &GridCell, Option<&RaycastPickTarget>), With<Platform>>,
+    rings: Query<Entity, With<AllowedRing>>,
+) {
+    // the new cells exist once the board is ready
+    if ready.iter().count() == 0 && !allowed.is_changed() {
+        return;
+    }
+    for ring in rings.iter() {
+        commands.entity(ring).despawn_recursive();
+    }
+
+    let mesh = meshes.add(
+        Torus {
+            radius: settings.cell_size.x * 0.9,
+            ring_radius: 0.15,
+            ..default()
+        }
+        .into(),
+    );
+    let material = materials.add(StandardMaterial {
+        base_color: Color::CYAN,
+        unlit: true,
+        ..default()
+    });
+    for (cell, hex, pickable) in cells.iter() {
+        if !allowed.allows(hex.0) {
+            commands
+                .entity(cell)
+                .remove::<(RaycastPickTarget, Hovered)>();
+            continue;
+        }
+        if pickable.is_none() {
+            commands.entity(cell).insert(RaycastPickTarget::default());
+        }
+        if allowed.0.is_some() {
+            commands.entity(cell).with_children(|parent| {
+                parent.spawn((
+                    PbrBundle {
+                        mesh: mesh.clone(),
+                        material: material.clone(),
+                        // on top of the platform
+                        transform: Transform::from_xyz(0., settings.column_height / 2., 0.),
+                        ..default()
+                    },
+                    Name::from("Allowed cell"),
+                    AllowedRing,
+                ));
+            });
+        }
+    }
+}
+
+/// Panel at the bottom of the screen with a heading, instructions and
+/// buttons, each a `(button, text)`
+fn spawn_panel<B: Component>(
+    commands: &mut Commands,
+    fonts: &FontAssets,
+    marker: impl Component,
+    heading: String,
+    text: String,
+    buttons: Vec<(B, String)>,
+) {
+    let text_style = |size| TextStyle {
+        font: fonts.fira_sans.clone(),
+        font_size: size,
+        color: Color::BLACK,
+    };
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        bottom: Val::Px(10.),
+                        left: Val::Percent(20.),
+                        ..default()
+                    },
+                    size: Size::width(Val::Percent(60.)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(10.)),
+                    gap: Size::all(Val::Px(10.)),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::WHITE.with_a(0.8)),
+                ..default()
+            },
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((TextBundle::from_section(heading, text_style(18.)),));
+            parent.spawn((TextBundle::from_section(text, text_style(24.)),));
+            parent
+                .spawn((NodeBundle {
+                    style: Style {
+                        gap: Size::all(Val::Px(10.)),
+                        ..default()
+                    },
+                    ..default()
+                },))
+                .with_children(|parent| {
+                    for (button, text) in buttons {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        padding: UiRect::all(Val::Px(5.)),
+                                        ..default()
+                                    },
+                                    background_color: BackgroundColor(Color::WHITE.with_a(0.8)),
+                                    ..default()
+                                },
+                                button,
+                            ))
+                            .with_children(|parent| {
+                                let mut text = TextBundle::from_section(text, text_style(18.));
+                                text.focus_policy = FocusPolicy::Pass;
+                                parent.spawn((text,));
+                            });
+                    }
+                });
+        });
+}
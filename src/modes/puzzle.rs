@@ -0,0 +1,394 @@
+//! Puzzles from `assets/puzzles`: win within a number of moves while the
+//! computer plays the defense.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{
+    cats::animation::AnimationQueue,
+    config::{GameConfig, PlayerConfig, PlayerKind, Rules},
+    events::{NewCat, PlaceCat, ResetGameEvent},
+    grid::Map,
+    history::MoveHistory,
+    loading::{FontAssets, PuzzleAssets},
+    locale::Locale,
+    players::{ComputerTurnTimer, Players},
+    puzzles::{Puzzle, PuzzleLoader, PuzzleSet},
+    rules::{Game, Outcome},
+    settings::Settings,
+    storage, GameState,
+};
+
+use super::{spawn_panel, AllowedCells, GameMode, StartMode};
+
+/// Name the solved puzzles are stored under, see [`storage`]
+const STORAGE_NAME: &str = "puzzles";
+
+pub struct PuzzlePlugin;
+
+impl Plugin for PuzzlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<PuzzleSet>();
+        app.init_asset_loader::<PuzzleLoader>();
+        app.insert_resource(storage::load::<PuzzleProgress>(STORAGE_NAME).unwrap_or_default());
+        app.add_event::<StartPuzzle>();
+
+        app.add_system(
+            start_puzzle
+                .run_if(on_event::<StartPuzzle>())
+                .run_if(resource_exists::<PuzzleAssets>()),
+        );
+        app.add_system(
+            reset_puzzle
+                .run_if(on_event::<ResetGameEvent>())
+                .run_if(in_puzzle),
+        );
+        app.add_system(
+            follow_moves
+                .run_if(on_event::<NewCat>())
+                .run_if(in_puzzle)
+                .in_base_set(CoreSet::PostUpdate),
+        );
+        app.add_system(
+            play_defense
+                .run_if(in_puzzle)
+                .in_set(OnUpdate(GameState::Playing)),
+        );
+        app.add_systems((
+            puzzle_buttons.run_if(in_puzzle),
+            show_panel
+                .run_if(resource_exists::<FontAssets>())
+                .run_if(panel_outdated),
+        ));
+        app.add_system(save_progress.run_if(resource_changed::<PuzzleProgress>()));
+    }
+}
+
+fn in_puzzle(mode: Res<GameMode>, active: Option<Res<ActivePuzzle>>) -> bool {
+    *mode == GameMode::Puzzle && active.is_some()
+}
+
+/// Play the puzzle at this index of the shipped ones
+#[derive(Debug)]
+pub struct StartPuzzle(pub usize);
+
+/// Ids of the puzzles that have been solved
+#[derive(Debug, Default, Resource, Serialize, Deserialize)]
+pub struct PuzzleProgress {
+    pub solved: Vec<String>,
+}
+
+impl PuzzleProgress {
+    pub fn is_solved(&self, id: &str) -> bool {
+        self.solved.iter().any(|solved| solved == id)
+    }
+}
+
+fn save_progress(progress: Res<PuzzleProgress>) {
+    if progress.is_added() {
+        return;
+    }
+    storage::save(STORAGE_NAME, &*progress);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PuzzleState {
+    Playing,
+    Solved,
+    Failed,
+}
+
+/// Puzzle being played, and its position followed with the headless rules
+/// to find the defense
+#[derive(Debug, Resource)]
+struct ActivePuzzle {
+    index: usize,
+    count: usize,
+    puzzle: Puzzle,
+    game: Game,
+    /// Moves made by the player
+    moves: u32,
+    /// Moves made by the defense
+    replies: usize,
+    state: PuzzleState,
+}
+
+impl ActivePuzzle {
+    fn moves_left(&self) -> u32 {
+        self.puzzle.moves.saturating_sub(self.moves)
+    }
+}
+
+#[instrument(level = "debug", skip_all)]
+fn start_puzzle(
+    mut commands: Commands,
+    assets: Res<PuzzleAssets>,
+    puzzle_sets: Res<Assets<PuzzleSet>>,
+    locale: Locale,
+    settings: Res<Settings>,
+    mut allowed: ResMut<AllowedCells>,
+    mut events: EventReader<StartPuzzle>,
+    mut starts: EventWriter<StartMode>,
+) {
+    let Some(StartPuzzle(index)) = events.iter().last() else {
+        return;
+    };
+    let Some(puzzles) = puzzle_sets.get(&assets.puzzles) else {
+        error!("Puzzles not loaded");
+        return;
+    };
+    let Some(puzzle) = puzzles.puzzles.get(*index) else {
+        warn!(index, "There is no such puzzle");
+        return;
+    };
+    info!(id = ?puzzle.id, "Starting puzzle");
+
+    let colors = settings.palette.colors();
+    let player = |name: &str, color, kind| PlayerConfig {
+        name: locale.t(name),
+        color,
+        kind,
+    };
+    let mut players = vec![
+        player("puzzle-player", colors[0], PlayerKind::Human),
+        player("puzzle-defense", colors[1], PlayerKind::Computer),
+    ];
+    // the one to solve it is the current player of the position
+    if puzzle.attacker().0 == 1 {
+        players.swap(0, 1);
+    }
+    starts.send(StartMode {
+        mode: GameMode::Puzzle,
+        config: GameConfig {
+            players,
+            ..default()
+        },
+        position: puzzle.position.clone(),
+    });
+    commands.insert_resource(ActivePuzzle {
+        index: *index,
+        count: puzzles.puzzles.len(),
+        puzzle: puzzle.clone(),
+        game: puzzle.game(),
+        moves: 0,
+        replies: 0,
+        state: PuzzleState::Playing,
+    });
+    allowed.0 = None;
+}
+
+/// Every (re)start of the puzzle starts from its position again
+fn reset_puzzle(mut active: ResMut<ActivePuzzle>, mut allowed: ResMut<AllowedCells>) {
+    active.game = active.puzzle.game();
+    active.moves = 0;
+    active.replies = 0;
+    active.state = PuzzleState::Playing;
+    allowed.0 = None;
+}
+
+/// Play the moves on the headless game too, and see whether the puzzle is
+/// solved
+#[instrument(level = "debug", skip_all)]
+fn follow_moves(
+    mut active: ResMut<ActivePuzzle>,
+    mut progress: ResMut<PuzzleProgress>,
+    mut allowed: ResMut<AllowedCells>,
+    mut new_cats: EventReader<NewCat>,
+) {
+    let attacker = active.puzzle.attacker();
+    for NewCat {
+        player,
+        cat,
+        position,
+        ..
+    } in new_cats.iter()
+    {
+        if active.state != PuzzleState::Playing {
+            continue;
+        }
+        if let Err(error) = active.game.place(*position, *cat) {
+            error!(
+                ?error,
+                ?position,
+                "Puzzle position out of sync with the board"
+            );
+            continue;
+        }
+        if *player == attacker {
+            active.moves += 1;
+        } else {
+            active.replies += 1;
+        }
+        active.state = match active.game.outcome {
+            Some(Outcome::Winner(winner)) if winner == attacker => PuzzleState::Solved,
+            Some(_) => PuzzleState::Failed,
+            None if active.moves_left() == 0 => PuzzleState::Failed,
+            None => PuzzleState::Playing,
+        };
+    }
+    if active.state == PuzzleState::Playing {
+        return;
+    }
+    debug!(id = ?active.puzzle.id, state = ?active.state, "Puzzle over");
+    // no more moves once it's decided
+    allowed.0 = Some(Vec::new());
+    if active.state == PuzzleState::Solved && !progress.is_solved(&active.puzzle.id) {
+        progress.solved.push(active.puzzle.id.clone());
+    }
+}
+
+/// The computer places the defending piece once the player's move played out
+#[instrument(level = "debug", skip_all)]
+fn play_defense(
+    time: Res<Time>,
+    active: Res<ActivePuzzle>,
+    map: Res<Map>,
+    queue: Res<AnimationQueue>,
+    history: Res<MoveHistory>,
+    mut timer: ResMut<ComputerTurnTimer>,
+    mut players: ResMut<Players>,
+    mut place: EventWriter<PlaceCat>,
+) {
+    if active.state != PuzzleState::Playing
+        || players.current().kind != PlayerKind::Computer
+        || !queue.is_idle()
+        || history.viewing.is_some()
+    {
+        timer.reset();
+        return;
+    }
+    if !timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    timer.reset();
+
+    let Some((hex, cat)) = active
+        .puzzle
+        .defend(&active.game, active.replies, active.moves_left())
+    else {
+        warn!("Defense has no move");
+        return;
+    };
+    let Some(cell) = map.cell_by_hex(hex) else {
+        error!(?hex, "Defense move is not on the board");
+        return;
+    };
+    debug!(?hex, ?cat, "Defense places cat");
+    players.selected_piece = cat;
+    place.send(PlaceCat { cell });
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+enum PuzzleButton {
+    Next,
+    Retry,
+    Quit,
+}
+
+#[instrument(level = "debug", skip_all)]
+fn puzzle_buttons(
+    active: Res<ActivePuzzle>,
+    buttons: Query<(&Interaction, &PuzzleButton), Changed<Interaction>>,
+    mut starts: EventWriter<StartPuzzle>,
+    mut reset: EventWriter<ResetGameEvent>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        debug!(?button, "Puzzle button clicked");
+        match button {
+            PuzzleButton::Next if active.index + 1 < active.count => {
+                starts.send(StartPuzzle(active.index + 1));
+            }
+            PuzzleButton::Next | PuzzleButton::Quit => next_state.set(GameState::Menu),
+            PuzzleButton::Retry => {
+                reset.send(ResetGameEvent);
+                next_state.set(GameState::Playing);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Component)]
+struct PuzzlePanel;
+
+fn panel_outdated(
+    mode: Res<GameMode>,
+    active: Option<Res<ActivePuzzle>>,
+    settings: Res<Settings>,
+) -> bool {
+    mode.is_changed() || settings.is_changed() || active.map_or(false, |active| active.is_changed())
+}
+
+/// What to do and how many moves are left, at the bottom of the screen
+#[instrument(level = "debug", skip_all)]
+fn show_panel(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Locale,
+    mode: Res<GameMode>,
+    settings: Res<Settings>,
+    active: Option<Res<ActivePuzzle>>,
+    previous: Query<Entity, With<PuzzlePanel>>,
+) {
+    for entity in previous.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let Some(active) = active.filter(|_| *mode == GameMode::Puzzle) else {
+        return;
+    };
+
+    let text = match active.state {
+        PuzzleState::Playing if active.puzzle.position.rules == Rules::Graduation => {
+            let key = format!("{:?}", settings.keys.switch_piece);
+            format!(
+                "{}\n{}",
+                goal(&locale, &active),
+                locale.t_args("menu-switch-piece-hint", &[("key", key.into())])
+            )
+        }
+        PuzzleState::Playing => goal(&locale, &active),
+        PuzzleState::Solved => locale.t("puzzle-solved"),
+        PuzzleState::Failed => locale.t("puzzle-failed"),
+    };
+    let last = active.index + 1 == active.count;
+    let mut buttons = Vec::new();
+    match active.state {
+        PuzzleState::Solved if last => buttons.push((PuzzleButton::Next, "puzzle-finish")),
+        PuzzleState::Solved => buttons.push((PuzzleButton::Next, "puzzle-next")),
+        PuzzleState::Failed => buttons.push((PuzzleButton::Retry, "puzzle-retry")),
+        PuzzleState::Playing => {}
+    }
+    buttons.push((PuzzleButton::Quit, "puzzle-quit"));
+
+    spawn_panel(
+        &mut commands,
+        &fonts,
+        PuzzlePanel,
+        locale.t_args(
+            "puzzle-title",
+            &[
+                ("number", (active.index + 1).into()),
+                ("count", active.count.into()),
+            ],
+        ),
+        text,
+        buttons
+            .into_iter()
+            .map(|(button, text_id)| (button, locale.t(text_id)))
+            .collect(),
+    );
+}
+
+fn goal(locale: &Locale, active: &ActivePuzzle) -> String {
+    locale.t_args(
+        "puzzle-goal",
+        &[
+            ("moves", active.puzzle.moves.into()),
+            ("left", active.moves_left().into()),
+        ],
+    )
+}
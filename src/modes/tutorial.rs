@@ -0,0 +1,437 @@
+//! Step by step introduction to the rules, each step a prepared position
+//! where one move shows how something works.
+
+use bevy::prelude::*;
+use hexx::Hex;
+use tracing::instrument;
+
+use crate::{
+    cats::{animation::AnimationQueue, Cat},
+    config::{GameConfig, PlayerConfig, PlayerKind, Rules},
+    events::{CatsGraduated, MoveCat, NewCat, ResetGameEvent, WinEvent},
+    loading::FontAssets,
+    locale::Locale,
+    players::PlayerId,
+    rules::SavedGame,
+    settings::Settings,
+    sound::{Cue, PlaySound},
+    GameState,
+};
+
+use super::{spawn_panel, AllowedCells, GameMode, StartMode};
+
+/// Board size of all steps, small enough to fall off quickly
+const RADIUS: u32 = 2;
+const LEARNER: PlayerId = PlayerId(0);
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Tutorial>();
+        app.add_event::<StartTutorial>();
+
+        app.add_system(start_tutorial.run_if(on_event::<StartTutorial>()));
+        app.add_system(
+            reset_step
+                .run_if(on_event::<ResetGameEvent>())
+                .run_if(in_tutorial),
+        );
+        app.add_systems(
+            (watch_move, check_move)
+                .chain()
+                .distributive_run_if(in_tutorial)
+                .in_base_set(CoreSet::PostUpdate),
+        );
+        app.add_systems((
+            tutorial_buttons.run_if(in_tutorial),
+            show_panel
+                .run_if(resource_exists::<FontAssets>())
+                .run_if(panel_outdated),
+        ));
+    }
+}
+
+fn in_tutorial(mode: Res<GameMode>) -> bool {
+    *mode == GameMode::Tutorial
+}
+
+/// Start the tutorial from its first step
+#[derive(Debug)]
+pub struct StartTutorial;
+
+/// What the move of a step has to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Goal {
+    Place,
+    Boop,
+    /// Nothing gets booped
+    Stay,
+    BoopOff,
+    Graduate,
+    Win,
+}
+
+#[derive(Debug, Clone)]
+struct Step {
+    /// Translation id of the instructions
+    text_id: &'static str,
+    /// Translation id of what the move did, once it's done
+    done_id: &'static str,
+    rules: Rules,
+    /// Pieces of the learner and of the other player
+    pieces: Vec<(Hex, Cat, PlayerId)>,
+    /// Kittens and cats the learner has
+    inventory: (u8, u8),
+    /// The only cells that can be clicked
+    targets: Vec<Hex>,
+    /// Piece that has to be placed on one of them
+    piece: Cat,
+    goal: Goal,
+}
+
+impl Step {
+    fn position(&self) -> SavedGame {
+        SavedGame {
+            rules: self.rules,
+            radius: RADIUS,
+            inventories: vec![self.inventory, (8, 0)],
+            current: LEARNER.0,
+            pieces: self
+                .pieces
+                .iter()
+                .map(|(hex, cat, owner)| ((hex.x, hex.y), *cat, owner.0))
+                .collect(),
+        }
+    }
+}
+
+fn steps() -> Vec<Step> {
+    let other = PlayerId(1);
+    vec![
+        Step {
+            text_id: "tutorial-place",
+            done_id: "tutorial-place-done",
+            rules: Rules::ThreeKittens,
+            pieces: Vec::new(),
+            inventory: (8, 0),
+            targets: vec![Hex::ZERO],
+            piece: Cat::Kitten,
+            goal: Goal::Place,
+        },
+        Step {
+            text_id: "tutorial-boop",
+            done_id: "tutorial-boop-done",
+            rules: Rules::ThreeKittens,
+            pieces: vec![(Hex::new(1, 0), Cat::Kitten, other)],
+            inventory: (7, 0),
+            targets: vec![Hex::ZERO],
+            piece: Cat::Kitten,
+            goal: Goal::Boop,
+        },
+        Step {
+            text_id: "tutorial-kitten-cat",
+            done_id: "tutorial-kitten-cat-done",
+            rules: Rules::Graduation,
+            pieces: vec![(Hex::new(1, 0), Cat::Adult, other)],
+            inventory: (7, 0),
+            targets: vec![Hex::ZERO],
+            piece: Cat::Kitten,
+            goal: Goal::Stay,
+        },
+        Step {
+            text_id: "tutorial-cats",
+            done_id: "tutorial-cats-done",
+            rules: Rules::Graduation,
+            pieces: vec![(Hex::new(1, 0), Cat::Adult, other)],
+            inventory: (7, 1),
+            targets: vec![Hex::ZERO],
+            piece: Cat::Adult,
+            goal: Goal::Boop,
+        },
+        Step {
+            text_id: "tutorial-fall-off",
+            done_id: "tutorial-fall-off-done",
+            rules: Rules::ThreeKittens,
+            pieces: vec![(Hex::new(2, 0), Cat::Kitten, other)],
+            inventory: (7, 0),
+            targets: vec![Hex::new(1, 0)],
+            piece: Cat::Kitten,
+            goal: Goal::BoopOff,
+        },
+        Step {
+            text_id: "tutorial-graduate",
+            done_id: "tutorial-graduate-done",
+            rules: Rules::Graduation,
+            pieces: vec![
+                (Hex::new(-1, 0), Cat::Kitten, LEARNER),
+                (Hex::ZERO, Cat::Kitten, LEARNER),
+            ],
+            inventory: (6, 0),
+            targets: vec![Hex::new(1, 0)],
+            piece: Cat::Kitten,
+            goal: Goal::Graduate,
+        },
+        Step {
+            text_id: "tutorial-win",
+            done_id: "tutorial-win-done",
+            rules: Rules::Graduation,
+            pieces: vec![
+                (Hex::new(-1, 0), Cat::Adult, LEARNER),
+                (Hex::ZERO, Cat::Adult, LEARNER),
+            ],
+            inventory: (4, 1),
+            targets: vec![Hex::new(1, 0)],
+            piece: Cat::Adult,
+            goal: Goal::Win,
+        },
+    ]
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum StepState {
+    /// Waiting for the move
+    #[default]
+    Waiting,
+    /// Checked once its animations are done
+    Moved,
+    Solved,
+    /// The move didn't do what the step is about
+    Failed {
+        wrong_piece: bool,
+    },
+}
+
+/// What the move of the current step did
+#[derive(Debug, Default)]
+struct Outcome {
+    placed: Option<(Hex, Cat)>,
+    boops: usize,
+    booped_off: usize,
+    graduated: bool,
+    won: bool,
+}
+
+#[derive(Debug, Default, Resource)]
+struct Tutorial {
+    step: usize,
+    state: StepState,
+    outcome: Outcome,
+}
+
+/// Start `step` with the learner and the other player from the palette
+fn start_step(
+    step: &Step,
+    locale: &Locale,
+    settings: &Settings,
+    starts: &mut EventWriter<StartMode>,
+) {
+    let colors = settings.palette.colors();
+    let player = |name: &str, color| PlayerConfig {
+        name: locale.t(name),
+        color,
+        kind: PlayerKind::Human,
+    };
+    starts.send(StartMode {
+        mode: GameMode::Tutorial,
+        config: GameConfig {
+            players: vec![
+                player("tutorial-learner", colors[0]),
+                player("tutorial-other", colors[1]),
+            ],
+            ..default()
+        },
+        position: step.position(),
+    });
+}
+
+fn start_tutorial(
+    locale: Locale,
+    settings: Res<Settings>,
+    mut tutorial: ResMut<Tutorial>,
+    mut starts: EventWriter<StartMode>,
+) {
+    *tutorial = Tutorial::default();
+    start_step(&steps()[0], &locale, &settings, &mut starts);
+}
+
+/// Every (re)start of a step starts over, with only its targets clickable
+fn reset_step(mut tutorial: ResMut<Tutorial>, mut allowed: ResMut<AllowedCells>) {
+    tutorial.state = StepState::Waiting;
+    tutorial.outcome = Outcome::default();
+    allowed.0 = steps().get(tutorial.step).map(|step| step.targets.clone());
+}
+
+#[instrument(level = "debug", skip_all)]
+fn watch_move(
+    mut tutorial: ResMut<Tutorial>,
+    mut allowed: ResMut<AllowedCells>,
+    mut new_cats: EventReader<NewCat>,
+    mut moves: EventReader<MoveCat>,
+    mut graduations: EventReader<CatsGraduated>,
+    mut wins: EventReader<WinEvent>,
+) {
+    for new_cat in new_cats.iter().filter(|new_cat| new_cat.player == LEARNER) {
+        tutorial.outcome.placed = Some((new_cat.position, new_cat.cat));
+        tutorial.state = StepState::Moved;
+        // one move per step
+        allowed.0 = Some(Vec::new());
+    }
+    for MoveCat { to, .. } in moves.iter() {
+        match to {
+            Some(_) => tutorial.outcome.boops += 1,
+            None => tutorial.outcome.booped_off += 1,
+        }
+    }
+    if graduations
+        .iter()
+        .any(|graduated| graduated.player == LEARNER)
+    {
+        tutorial.outcome.graduated = true;
+    }
+    if wins.iter().any(|win| win.winner == Some(LEARNER)) {
+        tutorial.outcome.won = true;
+    }
+}
+
+/// Once the move played out, see whether it did what the step asked for
+#[instrument(level = "debug", skip_all)]
+fn check_move(
+    queue: Res<AnimationQueue>,
+    mut tutorial: ResMut<Tutorial>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    if tutorial.state != StepState::Moved || !queue.is_idle() {
+        return;
+    }
+    let Some(step) = steps().into_iter().nth(tutorial.step) else {
+        return;
+    };
+    let outcome = &tutorial.outcome;
+    let Some((hex, cat)) = outcome.placed else {
+        return;
+    };
+    let reached = match step.goal {
+        Goal::Place => true,
+        Goal::Boop => outcome.boops > 0,
+        Goal::Stay => outcome.boops == 0 && outcome.booped_off == 0,
+        Goal::BoopOff => outcome.booped_off > 0,
+        Goal::Graduate => outcome.graduated,
+        Goal::Win => outcome.won,
+    };
+    let wrong_piece = cat != step.piece;
+    tutorial.state = if step.targets.contains(&hex) && !wrong_piece && reached {
+        sounds.send(Cue::Win.into());
+        StepState::Solved
+    } else {
+        StepState::Failed { wrong_piece }
+    };
+    debug!(step = tutorial.step, state = ?tutorial.state, "Checked tutorial move");
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+enum TutorialButton {
+    Next,
+    Retry,
+    Quit,
+}
+
+#[instrument(level = "debug", skip_all)]
+fn tutorial_buttons(
+    locale: Locale,
+    settings: Res<Settings>,
+    buttons: Query<(&Interaction, &TutorialButton), Changed<Interaction>>,
+    mut tutorial: ResMut<Tutorial>,
+    mut starts: EventWriter<StartMode>,
+    mut reset: EventWriter<ResetGameEvent>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        debug!(?button, "Tutorial button clicked");
+        match button {
+            TutorialButton::Next => {
+                let Some(step) = steps().into_iter().nth(tutorial.step + 1) else {
+                    next_state.set(GameState::Menu);
+                    continue;
+                };
+                tutorial.step += 1;
+                start_step(&step, &locale, &settings, &mut starts);
+            }
+            TutorialButton::Retry => {
+                reset.send(ResetGameEvent);
+                next_state.set(GameState::Playing);
+            }
+            TutorialButton::Quit => next_state.set(GameState::Menu),
+        }
+    }
+}
+
+#[derive(Debug, Default, Component)]
+struct TutorialPanel;
+
+fn panel_outdated(mode: Res<GameMode>, tutorial: Res<Tutorial>, settings: Res<Settings>) -> bool {
+    mode.is_changed() || tutorial.is_changed() || settings.is_changed()
+}
+
+/// Instructions at the bottom of the screen, with the result of the move
+#[instrument(level = "debug", skip_all)]
+fn show_panel(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Locale,
+    mode: Res<GameMode>,
+    settings: Res<Settings>,
+    tutorial: Res<Tutorial>,
+    previous: Query<Entity, With<TutorialPanel>>,
+) {
+    for entity in previous.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let steps = steps();
+    if *mode != GameMode::Tutorial || tutorial.step >= steps.len() {
+        return;
+    }
+    let step = &steps[tutorial.step];
+    let last = tutorial.step + 1 == steps.len();
+
+    let key = format!("{:?}", settings.keys.switch_piece);
+    let instructions = match tutorial.state {
+        StepState::Solved => locale.t(step.done_id),
+        StepState::Failed { wrong_piece: true } => {
+            locale.t_args("tutorial-wrong-piece", &[("key", key.into())])
+        }
+        StepState::Failed { wrong_piece: false } => locale.t("tutorial-try-again"),
+        StepState::Waiting | StepState::Moved => {
+            locale.t_args(step.text_id, &[("key", key.into())])
+        }
+    };
+    let mut buttons = Vec::new();
+    match tutorial.state {
+        StepState::Solved if last => buttons.push((TutorialButton::Next, "tutorial-finish")),
+        StepState::Solved => buttons.push((TutorialButton::Next, "tutorial-next")),
+        StepState::Failed { .. } => buttons.push((TutorialButton::Retry, "tutorial-retry")),
+        StepState::Waiting | StepState::Moved => {}
+    }
+    buttons.push((TutorialButton::Quit, "tutorial-quit"));
+
+    spawn_panel(
+        &mut commands,
+        &fonts,
+        TutorialPanel,
+        locale.t_args(
+            "tutorial-step",
+            &[
+                ("step", (tutorial.step + 1).into()),
+                ("steps", steps.len().into()),
+            ],
+        ),
+        instructions,
+        buttons
+            .into_iter()
+            .map(|(button, text_id)| (button, locale.t(text_id)))
+            .collect(),
+    );
+}
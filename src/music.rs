@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use tracing::instrument;
+
+use crate::{gameplay::Tension, loading::MusicAssets, settings::Settings, GameState};
+
+/// Seconds it takes for one track to fade into the next
+const CROSSFADE: f32 = 1.5;
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Music>();
+        app.add_system(play_music.run_if(not(in_state(GameState::Loading))));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Track {
+    Menu,
+    Game,
+    /// Someone is about to win
+    Tension,
+}
+
+/// Tracks that are currently playing, usually just one unless fading
+#[derive(Debug, Default, Resource)]
+struct Music {
+    channels: Vec<Channel>,
+}
+
+#[derive(Debug)]
+struct Channel {
+    track: Track,
+    sink: Handle<AudioSink>,
+    /// How far the track has faded in, from 0 to 1
+    level: f32,
+}
+
+/// Fade over to the track for the current state
+#[instrument(level = "trace", skip_all)]
+fn play_music(
+    time: Res<Time>,
+    state: Res<State<GameState>>,
+    tension: Res<Tension>,
+    settings: Res<Settings>,
+    tracks: Res<MusicAssets>,
+    audio: Res<Audio>,
+    sinks: Res<Assets<AudioSink>>,
+    mut music: ResMut<Music>,
+) {
+    let wanted = match state.0 {
+        GameState::Loading | GameState::Menu => Track::Menu,
+        GameState::Playing | GameState::Paused if tension.0 => Track::Tension,
+        GameState::Playing | GameState::Paused | GameState::GameOver => Track::Game,
+    };
+
+    if !music.channels.iter().any(|channel| channel.track == wanted) {
+        debug!(track = ?wanted, "Starting music");
+        let source = match wanted {
+            Track::Menu => tracks.menu.clone(),
+            Track::Game => tracks.game.clone(),
+            Track::Tension => tracks.tension.clone(),
+        };
+        // Browsers keep audio suspended until the first click or key press
+        // (see `restart-audio-context.js`), the track starts once it's resumed
+        let sink = audio.play_with_settings(source, PlaybackSettings::LOOP.with_volume(0.));
+        music.channels.push(Channel {
+            track: wanted,
+            sink: sinks.get_handle(sink),
+            level: 0.,
+        });
+    }
+
+    let step = time.delta_seconds() / CROSSFADE;
+    let volume = if settings.muted {
+        0.
+    } else {
+        settings.music_volume
+    };
+    music.channels.retain_mut(|channel| {
+        channel.level = if channel.track == wanted {
+            (channel.level + step).min(1.)
+        } else {
+            (channel.level - step).max(0.)
+        };
+
+        // sinks show up a frame after the track was started
+        let Some(sink) = sinks.get(&channel.sink) else {
+            return true;
+        };
+        if channel.level == 0. && channel.track != wanted {
+            debug!(track = ?channel.track, "Stopping music");
+            // dropping the sink would leave it playing
+            sink.stop();
+            return false;
+        }
+        sink.set_volume(channel.level * volume);
+        true
+    });
+}
@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use tracing::instrument;
+
+use crate::{
+    cats::{animation::AnimationQueue, Cat},
+    config::{GameConfig, PlayerKind, Rules},
+    events::PlaceCat,
+    grid::Map,
+    history::MoveHistory,
+};
+
+use super::Players;
+
+/// Small delay before computer players make their move, so humans can follow
+#[derive(Debug, Resource, Deref, DerefMut)]
+pub struct ComputerTurnTimer(Timer);
+
+impl Default for ComputerTurnTimer {
+    fn default() -> Self {
+        Self(Timer::new(Duration::from_millis(600), TimerMode::Once))
+    }
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn play_turn(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    map: Res<Map>,
+    history: Res<MoveHistory>,
+    queue: Res<AnimationQueue>,
+    mut timer: ResMut<ComputerTurnTimer>,
+    mut players: ResMut<Players>,
+    mut place: EventWriter<PlaceCat>,
+) {
+    let player = players.current();
+    // waits while an earlier move is viewed
+    if player.kind != PlayerKind::Computer || history.viewing.is_some() {
+        timer.reset();
+        return;
+    }
+    // like human players, wait until the last move has played out
+    if !queue.is_idle() {
+        return;
+    }
+
+    if !timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    timer.reset();
+
+    let free_cells = map
+        .cells
+        .iter()
+        .filter(|(hex, _)| map.cat_by_hex(**hex).is_none())
+        .map(|(_, cell)| *cell)
+        .collect::<Vec<_>>();
+    if free_cells.is_empty() {
+        warn!("Computer player has no free cell to place a cat on");
+        return;
+    }
+
+    if config.rules == Rules::Graduation && player.inventory.cats > 0 && fastrand::bool() {
+        players.selected_piece = Cat::Adult;
+    }
+
+    let cell = free_cells[fastrand::usize(..free_cells.len())];
+    debug!(?cell, "Computer places cat");
+    place.send(PlaceCat { cell });
+}
@@ -0,0 +1,302 @@
+//! Panels showing each player's pieces, what happened to them and how long
+//! the current turn is taking
+
+use bevy::{prelude::*, time::Stopwatch, window::PrimaryWindow};
+
+use crate::{
+    cats::{Cat, Meowple},
+    config::{adult_color, GameConfig, Rules},
+    gameplay::Clocks,
+    grid::GridCell,
+    loading::FontAssets,
+    locale::Locale,
+    settings::Settings,
+};
+
+use super::{Player, PlayerId, Players};
+
+const KITTEN_ICON_SIZE: f32 = 12.;
+const CAT_ICON_SIZE: f32 = 18.;
+
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct PlayerInfoPanel;
+
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct PlayerPanel(PlayerId);
+
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct TurnTimerText;
+
+/// Time left under the game's time control
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct ClockText(PlayerId);
+
+/// Time the current player has spent on their turn
+#[derive(Debug, Default, Resource)]
+pub struct TurnTimer {
+    stopwatch: Stopwatch,
+    player: Option<PlayerId>,
+}
+
+/// Spawn an empty panel for each player, [`show_players`] fills them
+pub fn setup(
+    mut commands: Commands,
+    players: Res<Players>,
+    mut timer: ResMut<TurnTimer>,
+    previous: Query<Entity, With<PlayerInfoPanel>>,
+) {
+    for entity in previous.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    *timer = TurnTimer::default();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::width(Val::Percent(100.)),
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Start,
+                    padding: UiRect::all(Val::Px(30.)),
+                    ..default()
+                },
+                ..default()
+            },
+            PlayerInfoPanel,
+        ))
+        .with_children(|panel| {
+            for player in &players.players {
+                panel.spawn((
+                    NodeBundle {
+                        style: Style {
+                            size: Size::width(Val::Px(200.)),
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Start,
+                            gap: Size::height(Val::Px(4.)),
+                            padding: UiRect::all(Val::Px(30.)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    PlayerPanel(player.id),
+                ));
+            }
+        });
+}
+
+/// Player info goes to the bottom of the screen on phones held upright
+pub fn layout_player_panel(
+    windows: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    new_panels: Query<(), Added<PlayerInfoPanel>>,
+    all_windows: Query<&Window, With<PrimaryWindow>>,
+    mut panel: Query<&mut Style, (With<PlayerInfoPanel>, Without<PlayerPanel>)>,
+    mut player_panels: Query<&mut Style, (With<PlayerPanel>, Without<PlayerInfoPanel>)>,
+) {
+    if windows.is_empty() && new_panels.is_empty() {
+        return;
+    }
+    let Ok(window) = all_windows.get_single() else {
+        return;
+    };
+    let portrait = window.height() > window.width();
+
+    for mut style in panel.iter_mut() {
+        style.position_type = PositionType::Absolute;
+        style.position = if portrait {
+            UiRect::bottom(Val::Px(0.))
+        } else {
+            UiRect::top(Val::Px(0.))
+        };
+        style.flex_wrap = if portrait {
+            FlexWrap::Wrap
+        } else {
+            FlexWrap::NoWrap
+        };
+        style.padding = UiRect::all(Val::Px(if portrait { 10. } else { 30. }));
+    }
+    for mut style in player_panels.iter_mut() {
+        style.size = if portrait {
+            Size::width(Val::Percent(50.))
+        } else {
+            Size::width(Val::Px(200.))
+        };
+        style.padding = UiRect::all(Val::Px(if portrait { 10. } else { 30. }));
+    }
+}
+
+pub fn cleanup(mut commands: Commands, query: Query<Entity, With<PlayerInfoPanel>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn inventory_text(locale: &Locale, player: &Player, rules: Rules) -> String {
+    let kittens = locale.t_args(
+        "kittens-count",
+        &[("count", player.inventory.kittens().into())],
+    );
+    match rules {
+        Rules::ThreeKittens => kittens,
+        Rules::Graduation => {
+            let cats = locale.t_args("cats-count", &[("count", player.inventory.cats().into())]);
+            format!("{kittens}, {cats}")
+        }
+    }
+}
+
+/// Panels need to be rebuilt when pieces were taken, placed or moved, or
+/// the language changed
+pub fn players_outdated(
+    players: Res<Players>,
+    settings: Res<Settings>,
+    new_panels: Query<(), Added<PlayerPanel>>,
+    moved: Query<(), (With<Meowple>, Changed<GridCell>)>,
+) -> bool {
+    players.is_changed() || settings.is_changed() || !new_panels.is_empty() || !moved.is_empty()
+}
+
+pub fn show_players(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Locale,
+    players: Res<Players>,
+    config: Res<GameConfig>,
+    timer: Res<TurnTimer>,
+    clocks: Res<Clocks>,
+    on_board: Query<&PlayerId, (With<Meowple>, With<GridCell>)>,
+    mut panels: Query<(Entity, &PlayerPanel, &mut BackgroundColor)>,
+) {
+    let current = players.current().id;
+    let text_style = |size: f32| TextStyle {
+        font: fonts.fira_sans.clone(),
+        font_size: size,
+        color: Color::BLACK,
+    };
+
+    for (entity, panel, mut background) in panels.iter_mut() {
+        let Some(player) = players.by_id(panel.0) else {
+            continue;
+        };
+        let is_current = player.id == current;
+        *background = BackgroundColor(player.color.with_a(if is_current { 0.8 } else { 0.4 }));
+
+        let pieces_on_board = on_board.iter().filter(|owner| **owner == player.id).count();
+        let stats = [
+            locale.t_args("hud-on-board", &[("count", pieces_on_board.into())]),
+            locale.t_args(
+                "hud-booped-off",
+                &[("count", player.stats.booped_off.into())],
+            ),
+            locale.t_args("hud-returned", &[("count", player.stats.returned.into())]),
+        ]
+        .join("\n");
+
+        let mut panel = commands.entity(entity);
+        panel.despawn_descendants();
+        panel.with_children(|info| {
+            info.spawn(TextBundle::from_section(
+                player.name.clone(),
+                text_style(20.),
+            ));
+            info.spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    align_items: AlignItems::End,
+                    gap: Size::all(Val::Px(4.)),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|icons| {
+                let kittens = (0..player.inventory.kittens()).map(|_| Cat::Kitten);
+                let cats = (0..player.inventory.cats()).map(|_| Cat::Adult);
+                for cat in kittens.chain(cats) {
+                    let (size, color) = match cat {
+                        Cat::Kitten => (KITTEN_ICON_SIZE, player.color),
+                        Cat::Adult => (CAT_ICON_SIZE, adult_color(player.color)),
+                    };
+                    icons.spawn(NodeBundle {
+                        style: Style {
+                            size: Size::all(Val::Px(size)),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(color),
+                        ..default()
+                    });
+                }
+            });
+            info.spawn(TextBundle::from_section(
+                inventory_text(&locale, player, config.rules),
+                text_style(16.),
+            ));
+            info.spawn(TextBundle::from_section(stats, text_style(14.)));
+            if is_current {
+                info.spawn((
+                    TextBundle::from_section(
+                        format_duration(timer.stopwatch.elapsed_secs()),
+                        text_style(16.),
+                    ),
+                    TurnTimerText,
+                ));
+            }
+            if let Some(remaining) = clocks.remaining(player.id) {
+                info.spawn((
+                    TextBundle::from_section(
+                        locale.t_args(
+                            "hud-clock",
+                            &[("time", format_duration(remaining.as_secs_f32()).into())],
+                        ),
+                        text_style(16.),
+                    ),
+                    ClockText(player.id),
+                ));
+            }
+        });
+    }
+}
+
+/// Start counting again whenever it's someone else's turn
+pub fn tick_turn_timer(time: Res<Time>, players: Res<Players>, mut timer: ResMut<TurnTimer>) {
+    let current = players.current().id;
+    if timer.player != Some(current) {
+        timer.player = Some(current);
+        timer.stopwatch.reset();
+    }
+    timer.stopwatch.tick(time.delta());
+}
+
+pub fn show_turn_timer(timer: Res<TurnTimer>, mut texts: Query<&mut Text, With<TurnTimerText>>) {
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = format_duration(timer.stopwatch.elapsed_secs());
+    }
+}
+
+/// Clocks turn red when time is running out
+pub fn show_clocks(locale: Locale, clocks: Res<Clocks>, mut texts: Query<(&mut Text, &ClockText)>) {
+    for (mut text, ClockText(player)) in texts.iter_mut() {
+        let Some(remaining) = clocks.remaining(*player) else {
+            continue;
+        };
+        let section = &mut text.sections[0];
+        section.value = locale.t_args(
+            "hud-clock",
+            &[("time", format_duration(remaining.as_secs_f32()).into())],
+        );
+        section.style.color = if clocks.is_low(*player) {
+            Color::RED
+        } else {
+            Color::BLACK
+        };
+    }
+}
+
+/// `m:ss`
+fn format_duration(seconds: f32) -> String {
+    let seconds = seconds as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
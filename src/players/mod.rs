@@ -2,10 +2,17 @@ use bevy::prelude::*;
 use std::fmt;
 use tracing::instrument;
 
+mod computer;
+mod hud;
 mod plugin;
+pub use computer::ComputerTurnTimer;
 pub use plugin::*;
 
-use crate::cats::Cat;
+use crate::{
+    cats::Cat,
+    config::{GameConfig, PlayerKind},
+    rules::{next_seat, SavedGame},
+};
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect, FromReflect, Component)]
 #[reflect(Component)]
@@ -28,42 +35,67 @@ impl fmt::Display for PlayerId {
 pub struct Players {
     pub players: Vec<Player>,
     current_player: usize,
+    /// Piece the current player is about to place
+    pub selected_piece: Cat,
 }
 
 impl Default for Players {
     fn default() -> Self {
+        Self::from_config(&GameConfig::default())
+    }
+}
+
+impl Players {
+    pub fn from_config(config: &GameConfig) -> Self {
         Self {
-            players: vec![
-                Player {
-                    id: PlayerId::new(0),
-                    name: "Green".into(),
+            players: config
+                .players
+                .iter()
+                .enumerate()
+                .map(|(idx, player)| Player {
+                    id: PlayerId::new(idx as u8),
+                    name: player.name.clone(),
                     inventory: Inventory::default(),
-                    color: Color::LIME_GREEN,
-                },
-                Player {
-                    id: PlayerId::new(1),
-                    name: "Orange".into(),
-                    inventory: Inventory::default(),
-                    color: Color::ORANGE,
-                },
-            ],
+                    stats: PlayerStats::default(),
+                    color: player.color,
+                    kind: player.kind,
+                })
+                .collect(),
             current_player: 0,
+            selected_piece: Cat::Kitten,
         }
     }
-}
 
-impl Players {
+    /// Players of `config` with the inventories of a prepared position, whose
+    /// player moves first
+    pub fn from_position(config: &GameConfig, position: &SavedGame) -> Self {
+        let mut players = Self::from_config(config).starting_with(position.current as usize);
+        for (player, (kittens, cats)) in players.players.iter_mut().zip(&position.inventories) {
+            player.inventory = Inventory::new(*kittens, *cats);
+        }
+        players
+    }
+
+    /// Player `idx` makes the first move
+    pub fn starting_with(mut self, idx: usize) -> Self {
+        self.current_player = idx % self.players.len();
+        self
+    }
+
     pub fn current(&self) -> &Player {
         &self.players[self.current_player]
     }
 
     #[instrument(level = "debug", skip_all)]
     pub fn next_player(&mut self) -> &Player {
-        let next_player = (self.current_player + 1).rem_euclid(self.players.len());
-        if self.players[next_player].can_do_turn() {
-            self.current_player = next_player;
-        } else {
-            debug!("Next player has no playable slots");
+        let count = self.players.len();
+        self.selected_piece = Cat::Kitten;
+        let next_player = next_seat(self.current_player, count, |idx| {
+            self.players[idx].can_do_turn()
+        });
+        match next_player {
+            Some(next_player) => self.current_player = next_player,
+            None => debug!("No player has playable slots"),
         }
         &self.players[self.current_player]
     }
@@ -91,6 +123,31 @@ impl Players {
         player.inventory.kittens += 1;
     }
 
+    /// `by` booped a `cat` of `owner` off the board, which goes back to
+    /// `owner`
+    #[instrument(level = "debug", skip_all)]
+    pub fn booped_off(&mut self, by: PlayerId, owner: PlayerId, cat: Cat) {
+        if let Some(player) = self.players.iter_mut().find(|p| p.id == by) {
+            player.stats.booped_off += 1;
+        }
+        if let Some(player) = self.players.iter_mut().find(|p| p.id == owner) {
+            player.stats.returned += 1;
+        }
+        match cat {
+            Cat::Kitten => self.gain_kitten(owner),
+            Cat::Adult => self.gain_cats(owner, 1),
+        }
+    }
+
+    /// Player is out of the game, their turns are skipped from now on
+    #[instrument(level = "debug", skip_all)]
+    pub fn forfeit(&mut self, player: PlayerId) {
+        if let Some(player) = self.players.iter_mut().find(|p| p.id == player) {
+            debug!(player = ?player.id, "Forfeit");
+            player.inventory = Inventory::new(0, 0);
+        }
+    }
+
     #[instrument(level = "debug", skip_all)]
     pub fn take_cat(&mut self) -> Option<Cat> {
         let mut player = &mut self.players[self.current_player];
@@ -103,9 +160,31 @@ impl Players {
         }
     }
 
+    /// Take the selected piece from the current player's inventory, falling
+    /// back to the other kind if there are none left
     #[instrument(level = "debug", skip_all)]
-    pub fn gain_cats(&mut self, num: u8) {
-        let mut player = &mut self.players[self.current_player];
+    pub fn take_piece(&mut self) -> Option<Cat> {
+        match self.selected_piece {
+            Cat::Kitten => self.take_kitten().or_else(|| self.take_cat()),
+            Cat::Adult => self.take_cat().or_else(|| self.take_kitten()),
+        }
+    }
+
+    /// Piece [`Players::take_piece`] would take, without taking it
+    pub fn next_piece(&self) -> Option<Cat> {
+        let inventory = &self.current().inventory;
+        let other = match self.selected_piece {
+            Cat::Kitten => Cat::Adult,
+            Cat::Adult => Cat::Kitten,
+        };
+        [self.selected_piece, other]
+            .into_iter()
+            .find(|cat| inventory.has(*cat))
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    pub fn gain_cats(&mut self, player: PlayerId, num: u8) {
+        let mut player = self.players.iter_mut().find(|p| p.id == player).unwrap();
         debug!(num, "More cats!");
         player.inventory.cats += num;
     }
@@ -116,12 +195,23 @@ pub struct Player {
     pub id: PlayerId,
     pub name: String,
     pub inventory: Inventory,
+    pub stats: PlayerStats,
     pub color: Color,
+    pub kind: PlayerKind,
+}
+
+/// What happened to a player's pieces this game
+#[derive(Debug, Clone, Default, PartialEq, Eq, Reflect, FromReflect)]
+pub struct PlayerStats {
+    /// Pieces of any player this player booped off the board
+    pub booped_off: u8,
+    /// Own pieces that fell off the board and came back
+    pub returned: u8,
 }
 
 impl Player {
     pub fn can_do_turn(&self) -> bool {
-        self.inventory.kittens + self.inventory.cats > 0
+        self.inventory.has_pieces()
     }
 }
 
@@ -131,6 +221,52 @@ pub struct Inventory {
     kittens: u8,
 }
 
+impl Inventory {
+    pub fn new(kittens: u8, cats: u8) -> Self {
+        Self { cats, kittens }
+    }
+
+    /// Whether there is at least one piece of this kind left
+    pub fn has(&self, cat: Cat) -> bool {
+        match cat {
+            Cat::Kitten => self.kittens > 0,
+            Cat::Adult => self.cats > 0,
+        }
+    }
+
+    /// Whether there is anything left to place
+    pub fn has_pieces(&self) -> bool {
+        self.kittens + self.cats > 0
+    }
+
+    pub fn kittens(&self) -> u8 {
+        self.kittens
+    }
+
+    pub fn cats(&self) -> u8 {
+        self.cats
+    }
+
+    /// Take a piece of this kind, if there is one left
+    pub fn take(&mut self, cat: Cat) -> bool {
+        if !self.has(cat) {
+            return false;
+        }
+        match cat {
+            Cat::Kitten => self.kittens -= 1,
+            Cat::Adult => self.cats -= 1,
+        }
+        true
+    }
+
+    pub fn gain(&mut self, cat: Cat, num: u8) {
+        match cat {
+            Cat::Kitten => self.kittens += num,
+            Cat::Adult => self.cats += num,
+        }
+    }
+}
+
 impl Default for Inventory {
     fn default() -> Self {
         Self {
@@ -2,12 +2,18 @@ use bevy::prelude::*;
 use tracing::instrument;
 
 use crate::{
+    cats::Cat,
+    config::{GameConfig, Rules},
     events::{NextPlayer, ResetGameEvent},
+    gameplay::Series,
     loading::FontAssets,
+    menu::key_bindings_active,
+    modes::{normal_game, StartPosition},
+    settings::Settings,
     GameState,
 };
 
-use super::{Player, PlayerId, Players};
+use super::{computer, hud, Players};
 
 pub struct PlayerPlugin;
 
@@ -16,164 +22,65 @@ impl Plugin for PlayerPlugin {
         app.init_resource::<Players>();
         app.register_type::<Players>();
 
-        app.add_system(setup.in_schedule(OnExit(GameState::Loading)));
+        app.init_resource::<computer::ComputerTurnTimer>();
+        app.init_resource::<hud::TurnTimer>();
+
         app.add_system(reset_players.run_if(on_event::<ResetGameEvent>()));
+        app.add_system(
+            hud::setup
+                .after(reset_players)
+                .run_if(on_event::<ResetGameEvent>()),
+        );
+        app.add_system(hud::cleanup.in_schedule(OnEnter(GameState::Menu)));
 
         app.add_systems((
-            show_players.run_if(resource_exists_and_changed::<Players>()),
-            show_current_player_indicator.run_if(resource_exists_and_changed::<Players>()),
+            hud::show_players
+                .after(hud::setup)
+                .run_if(resource_exists::<FontAssets>())
+                .run_if(hud::players_outdated),
+            hud::layout_player_panel,
             next_player.run_if(on_event::<NextPlayer>()),
         ));
-    }
-}
-
-#[derive(Debug, Default, Component, Reflect)]
-#[reflect(Component)]
-struct PlayerInfoPanel;
-
-#[derive(Debug, Default, Component, Reflect)]
-#[reflect(Component)]
-struct PlayerInfo(PlayerId);
-
-#[derive(Debug, Default, Component, Reflect)]
-#[reflect(Component)]
-struct CurrentPlayerIndicator(PlayerId);
-
-fn setup(mut commands: Commands, fonts: Res<FontAssets>) {
-    commands
-        .spawn((
-            NodeBundle {
-                style: Style {
-                    size: Size::width(Val::Percent(100.)),
-                    flex_direction: FlexDirection::Row,
-                    align_items: AlignItems::Start,
-                    padding: UiRect::all(Val::Px(30.)),
-                    ..default()
-                },
-                ..default()
-            },
-            PlayerInfoPanel,
-        ))
-        .with_children(|panel| {
-            panel
-                .spawn((NodeBundle {
-                    style: Style {
-                        size: Size::width(Val::Px(200.)),
-                        flex_direction: FlexDirection::Row,
-                        align_items: AlignItems::Start,
-                        padding: UiRect::all(Val::Px(30.)),
-                        ..default()
-                    },
-                    background_color: BackgroundColor(Color::LIME_GREEN.with_a(0.5)),
-                    ..default()
-                },))
-                .with_children(|player1| {
-                    player1.spawn((
-                        TextBundle::from_section(
-                            "6 kittens",
-                            TextStyle {
-                                font: fonts.fira_sans.clone(),
-                                font_size: 16.0,
-                                color: Color::BLACK,
-                            },
-                        )
-                        .with_text_alignment(TextAlignment::Left)
-                        .with_style(Style {
-                            size: Size::width(Val::Px(200.)),
-                            ..default()
-                        }),
-                        PlayerInfo(PlayerId::new(0)),
-                    ));
-                    player1.spawn((
-                        NodeBundle {
-                            style: Style {
-                                size: Size::all(Val::Px(20.)),
-                                ..default()
-                            },
-                            background_color: BackgroundColor(Color::LIME_GREEN),
-                            visibility: Visibility::Visible,
-                            ..default()
-                        },
-                        CurrentPlayerIndicator(PlayerId::new(0)),
-                    ));
-                });
-
-            panel
-                .spawn((NodeBundle {
-                    style: Style {
-                        size: Size::width(Val::Px(200.)),
-                        flex_direction: FlexDirection::Row,
-                        align_items: AlignItems::Start,
-                        padding: UiRect::all(Val::Px(30.)),
-                        ..default()
-                    },
-                    background_color: BackgroundColor(Color::ORANGE.with_a(0.5)),
-                    ..default()
-                },))
-                .with_children(|player2| {
-                    player2.spawn((
-                        TextBundle::from_section(
-                            "6 kittens",
-                            TextStyle {
-                                font: fonts.fira_sans.clone(),
-                                font_size: 16.0,
-                                color: Color::BLACK,
-                            },
-                        )
-                        .with_text_alignment(TextAlignment::Right)
-                        .with_style(Style {
-                            size: Size::width(Val::Px(200.)),
-                            ..default()
-                        }),
-                        PlayerInfo(PlayerId::new(1)),
-                    ));
-                    player2.spawn((
-                        NodeBundle {
-                            style: Style {
-                                size: Size::all(Val::Px(20.)),
-                                ..default()
-                            },
-                            background_color: BackgroundColor(Color::ORANGE),
-                            visibility: Visibility::Hidden,
-                            ..default()
-                        },
-                        CurrentPlayerIndicator(PlayerId::new(1)),
-                    ));
-                });
-        });
-}
-
-fn show_players(players: Res<Players>, mut info: Query<(&mut Text, &PlayerInfo)>) {
-    for (mut text, player) in info.iter_mut() {
-        let id = player.0;
-        let Player { inventory, .. } = players
-            .players
-            .iter()
-            .find(|p| p.id == id)
-            .expect("valid player id");
-        *text = Text::from_section(
-            format!("{} kittens", inventory.kittens),
-            text.sections[0].style.clone(),
+        app.add_systems(
+            (
+                select_piece.run_if(key_bindings_active),
+                computer::play_turn.run_if(normal_game),
+                hud::tick_turn_timer.before(hud::show_turn_timer),
+                hud::show_turn_timer,
+                hud::show_clocks,
+            )
+                .in_set(OnUpdate(GameState::Playing)),
         );
     }
 }
 
-fn show_current_player_indicator(
-    players: Res<Players>,
-    mut indicators: Query<(&mut Visibility, &CurrentPlayerIndicator)>,
+fn reset_players(
+    config: Res<GameConfig>,
+    series: Res<Series>,
+    start_position: Res<StartPosition>,
+    mut players: ResMut<Players>,
 ) {
-    let current = players.current().id;
-    for (mut vis, player) in indicators.iter_mut() {
-        *vis = if player.0 == current {
-            Visibility::Visible
-        } else {
-            Visibility::Hidden
-        };
-    }
+    *players = match &start_position.0 {
+        Some(position) => Players::from_position(&config, position),
+        None => Players::from_config(&config).starting_with(series.first_player),
+    };
 }
 
-fn reset_players(mut players: ResMut<Players>) {
-    *players = Players::default();
+/// Switch between placing kittens and adult cats
+fn select_piece(
+    keys: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    config: Res<GameConfig>,
+    mut players: ResMut<Players>,
+) {
+    if config.rules != Rules::Graduation || !keys.just_pressed(settings.keys.switch_piece) {
+        return;
+    }
+    players.selected_piece = match players.selected_piece {
+        Cat::Kitten => Cat::Adult,
+        Cat::Adult => Cat::Kitten,
+    };
+    debug!(piece = ?players.selected_piece, "Selected piece");
 }
 
 #[instrument(level = "info", skip_all)]
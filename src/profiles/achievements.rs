@@ -0,0 +1,265 @@
+//! Achievements unlocked by moves and games of local players
+
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{
+    config::GameConfig,
+    events::{CatsGraduated, MoveCat, WinEvent},
+    loading::FontAssets,
+    locale::Locale,
+    players::{PlayerId, Players},
+};
+
+use super::{has_profile, GameTally, Profiles};
+
+/// Wins in fewer moves than this are quick
+const QUICK_WIN_MOVES: u32 = 10;
+/// How long an unlock is shown
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Achievement {
+    /// Boop three pieces off the board with one move
+    TripleBoopOff,
+    /// Win without any own piece booped off the board
+    Flawless,
+    /// Win with fewer than [`QUICK_WIN_MOVES`] own moves
+    QuickWin,
+    /// Graduate pieces on two own moves in a row
+    DoubleGraduation,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 4] = [
+        Achievement::TripleBoopOff,
+        Achievement::Flawless,
+        Achievement::QuickWin,
+        Achievement::DoubleGraduation,
+    ];
+
+    /// Translation id of the name
+    pub fn label_id(&self) -> &'static str {
+        match self {
+            Achievement::TripleBoopOff => "achievement-triple-boop-off",
+            Achievement::Flawless => "achievement-flawless",
+            Achievement::QuickWin => "achievement-quick-win",
+            Achievement::DoubleGraduation => "achievement-double-graduation",
+        }
+    }
+
+    /// Translation id of what it takes to unlock it
+    pub fn description_id(&self) -> &'static str {
+        match self {
+            Achievement::TripleBoopOff => "achievement-triple-boop-off-description",
+            Achievement::Flawless => "achievement-flawless-description",
+            Achievement::QuickWin => "achievement-quick-win-description",
+            Achievement::DoubleGraduation => "achievement-double-graduation-description",
+        }
+    }
+}
+
+/// A player got an achievement for the first time
+#[derive(Debug)]
+pub struct AchievementUnlocked {
+    pub player: String,
+    pub achievement: Achievement,
+}
+
+/// Add the achievement to `player`'s profile, unless they have it already
+fn unlock(
+    config: &GameConfig,
+    players: &Players,
+    profiles: &mut Profiles,
+    player: PlayerId,
+    achievement: Achievement,
+    unlocked: &mut EventWriter<AchievementUnlocked>,
+) {
+    if !has_profile(config, player) {
+        return;
+    }
+    let Some(player) = players.by_id(player) else {
+        return;
+    };
+    let profile = profiles.get_or_create(&player.name);
+    if profile.achievements.contains(&achievement) {
+        return;
+    }
+    info!(name = ?player.name, ?achievement, "Achievement unlocked");
+    profile.achievements.push(achievement);
+    unlocked.send(AchievementUnlocked {
+        player: player.name.clone(),
+        achievement,
+    });
+}
+
+/// Boops of one move are all sent in the frame the piece is placed
+#[instrument(level = "debug", skip_all)]
+pub fn boop_achievements(
+    config: Res<GameConfig>,
+    players: Res<Players>,
+    mut profiles: ResMut<Profiles>,
+    mut moves: EventReader<MoveCat>,
+    mut unlocked: EventWriter<AchievementUnlocked>,
+) {
+    let mut booped_off = HashMap::<PlayerId, u32>::new();
+    for MoveCat { by, to, .. } in moves.iter() {
+        if to.is_none() {
+            *booped_off.entry(*by).or_default() += 1;
+        }
+    }
+    for (player, count) in booped_off {
+        if count >= 3 {
+            let achievement = Achievement::TripleBoopOff;
+            unlock(
+                &config,
+                &players,
+                &mut profiles,
+                player,
+                achievement,
+                &mut unlocked,
+            );
+        }
+    }
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn graduation_achievements(
+    config: Res<GameConfig>,
+    players: Res<Players>,
+    mut tally: ResMut<GameTally>,
+    mut profiles: ResMut<Profiles>,
+    mut graduations: EventReader<CatsGraduated>,
+    mut unlocked: EventWriter<AchievementUnlocked>,
+) {
+    for CatsGraduated { player, .. } in graduations.iter() {
+        let idx = tally.player(*player);
+        let this_move = tally.player_moves[idx];
+        let previous = tally.graduated_at[idx].replace(this_move);
+        if previous.map_or(false, |previous| previous + 1 == this_move) {
+            let achievement = Achievement::DoubleGraduation;
+            unlock(
+                &config,
+                &players,
+                &mut profiles,
+                *player,
+                achievement,
+                &mut unlocked,
+            );
+        }
+    }
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn win_achievements(
+    config: Res<GameConfig>,
+    players: Res<Players>,
+    mut tally: ResMut<GameTally>,
+    mut profiles: ResMut<Profiles>,
+    mut wins: EventReader<WinEvent>,
+    mut unlocked: EventWriter<AchievementUnlocked>,
+) {
+    // a game only ends once
+    let Some(winner) = wins.iter().next().and_then(|win| win.winner) else {
+        return;
+    };
+    let idx = tally.player(winner);
+    if tally.cats_lost[idx] == 0 {
+        let achievement = Achievement::Flawless;
+        unlock(
+            &config,
+            &players,
+            &mut profiles,
+            winner,
+            achievement,
+            &mut unlocked,
+        );
+    }
+    if tally.player_moves[idx] < QUICK_WIN_MOVES {
+        let achievement = Achievement::QuickWin;
+        unlock(
+            &config,
+            &players,
+            &mut profiles,
+            winner,
+            achievement,
+            &mut unlocked,
+        );
+    }
+}
+
+#[derive(Debug, Component)]
+pub struct Toast(Timer);
+
+/// Announce unlocked achievements at the top of the screen, newest last
+#[instrument(level = "debug", skip_all)]
+pub fn show_toasts(
+    mut commands: Commands,
+    fonts: Res<FontAssets>,
+    locale: Locale,
+    mut unlocked: EventReader<AchievementUnlocked>,
+    toasts: Query<(), With<Toast>>,
+) {
+    let shown = toasts.iter().count();
+    for (
+        idx,
+        AchievementUnlocked {
+            player,
+            achievement,
+        },
+    ) in unlocked.iter().enumerate()
+    {
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        position: UiRect {
+                            top: Val::Px(10. + 50. * (shown + idx) as f32),
+                            left: Val::Percent(35.),
+                            ..default()
+                        },
+                        size: Size::width(Val::Percent(30.)),
+                        justify_content: JustifyContent::Center,
+                        padding: UiRect::all(Val::Px(10.)),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(Color::WHITE.with_a(0.9)),
+                    z_index: ZIndex::Global(20),
+                    ..default()
+                },
+                Toast(Timer::new(TOAST_DURATION, TimerMode::Once)),
+            ))
+            .with_children(|parent| {
+                parent.spawn((TextBundle::from_section(
+                    locale.t_args(
+                        "achievement-unlocked",
+                        &[
+                            ("player", player.clone().into()),
+                            ("achievement", locale.t(achievement.label_id()).into()),
+                        ],
+                    ),
+                    TextStyle {
+                        font: fonts.fira_sans.clone(),
+                        font_size: 18.0,
+                        color: Color::BLACK,
+                    },
+                ),));
+            });
+    }
+}
+
+pub fn expire_toasts(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut toasts: Query<(Entity, &mut Toast)>,
+) {
+    for (entity, mut toast) in toasts.iter_mut() {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! Local player profiles with statistics over all their games, stored like
+//! the settings.
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use hexx::Hex;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{
+    cats::Meowple,
+    config::{GameConfig, PlayerKind},
+    events::{CatsGraduated, MoveCat, NewCat, ResetGameEvent, WinEvent},
+    gameplay,
+    loading::FontAssets,
+    modes::normal_game,
+    players::{PlayerId, Players},
+    settings::Settings,
+    storage,
+};
+
+mod achievements;
+mod rating;
+
+pub use achievements::Achievement;
+pub use rating::{Rated, RatingChanges, Ratings};
+
+/// Name the profiles are stored under, see [`storage`]
+const STORAGE_NAME: &str = "profiles";
+/// Name the ratings are stored under
+const RATINGS_STORAGE_NAME: &str = "ratings";
+
+pub struct ProfilesPlugin;
+
+impl Plugin for ProfilesPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(storage::load::<Profiles>(STORAGE_NAME).unwrap_or_default());
+        app.init_resource::<GameTally>();
+
+        app.add_system(clear_tally.run_if(on_event::<ResetGameEvent>()));
+        app.add_systems(
+            (
+                tally_moves.run_if(on_event::<NewCat>()),
+                tally_boops.run_if(on_event::<MoveCat>()),
+                achievements::boop_achievements
+                    .run_if(on_event::<MoveCat>())
+                    .run_if(normal_game),
+                achievements::graduation_achievements
+                    .run_if(on_event::<CatsGraduated>())
+                    .run_if(normal_game),
+                achievements::win_achievements
+                    .run_if(on_event::<WinEvent>())
+                    .run_if(normal_game),
+                record_games
+                    .run_if(on_event::<WinEvent>())
+                    .run_if(normal_game),
+            )
+                .chain()
+                .in_base_set(CoreSet::PostUpdate),
+        );
+
+        app.add_event::<achievements::AchievementUnlocked>();
+        app.add_systems(
+            (
+                achievements::show_toasts
+                    .run_if(on_event::<achievements::AchievementUnlocked>())
+                    .run_if(resource_exists::<FontAssets>()),
+                achievements::expire_toasts,
+            )
+                .in_base_set(CoreSet::PostUpdate)
+                .after(record_games),
+        );
+        app.add_system(save_profiles.run_if(resource_changed::<Profiles>()));
+
+        app.insert_resource(storage::load::<Ratings>(RATINGS_STORAGE_NAME).unwrap_or_default());
+        app.init_resource::<RatingChanges>();
+        app.add_system(
+            rating::update_ratings
+                .run_if(on_event::<WinEvent>())
+                .run_if(normal_game)
+                .before(gameplay::win_screen),
+        );
+        app.add_system(save_ratings.run_if(resource_changed::<Ratings>()));
+    }
+}
+
+/// Everyone who played on this computer
+#[derive(Debug, Clone, Default, PartialEq, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profiles {
+    pub profiles: Vec<Profile>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub name: String,
+    /// Slot of the color they last played with, the same in every palette
+    pub color: usize,
+    pub stats: ProfileStats,
+    /// In the order they were unlocked
+    pub achievements: Vec<Achievement>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileStats {
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub ties: u32,
+    /// Moves of all players in all finished games, for the average length
+    pub moves: u32,
+    /// Pieces of any player moved by their pieces
+    pub boops_dealt: u32,
+    /// Own pieces booped off the board
+    pub cats_lost: u32,
+    /// How often each cell was their first move, by axial coordinates
+    pub openings: BTreeMap<(i32, i32), u32>,
+}
+
+impl ProfileStats {
+    /// Moves per game
+    pub fn average_game_length(&self) -> Option<f32> {
+        (self.games > 0).then(|| self.moves as f32 / self.games as f32)
+    }
+
+    pub fn favorite_opening(&self) -> Option<Hex> {
+        self.openings
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|((x, y), _)| Hex::new(*x, *y))
+    }
+}
+
+impl Profiles {
+    /// Profile after the one called `name`, the first one for unknown names
+    pub fn next_after(&self, name: &str) -> Option<&Profile> {
+        let idx = self
+            .profiles
+            .iter()
+            .position(|profile| profile.name == name)
+            .map_or(0, |idx| idx + 1);
+        self.profiles.get(idx).or_else(|| self.profiles.first())
+    }
+
+    pub fn get_or_create(&mut self, name: &str) -> &mut Profile {
+        match self
+            .profiles
+            .iter()
+            .position(|profile| profile.name == name)
+        {
+            Some(idx) => &mut self.profiles[idx],
+            None => {
+                info!(?name, "New profile");
+                self.profiles.push(Profile {
+                    name: name.to_string(),
+                    ..default()
+                });
+                self.profiles.last_mut().expect("just added")
+            }
+        }
+    }
+}
+
+/// What happened so far in the current game, added to the profiles when it
+/// is over
+#[derive(Debug, Default, Resource)]
+pub struct GameTally {
+    moves: u32,
+    /// By player index
+    boops_dealt: Vec<u32>,
+    cats_lost: Vec<u32>,
+    openings: Vec<Option<Hex>>,
+    /// Moves each player made
+    player_moves: Vec<u32>,
+    /// Move of the player on which their pieces last graduated
+    graduated_at: Vec<Option<u32>>,
+}
+
+impl GameTally {
+    fn player(&mut self, player: PlayerId) -> usize {
+        let idx = player.0 as usize;
+        if self.boops_dealt.len() <= idx {
+            self.boops_dealt.resize(idx + 1, 0);
+            self.cats_lost.resize(idx + 1, 0);
+            self.openings.resize(idx + 1, None);
+            self.player_moves.resize(idx + 1, 0);
+            self.graduated_at.resize(idx + 1, None);
+        }
+        idx
+    }
+}
+
+/// Players who get statistics, computer players don't
+pub fn has_profile(config: &GameConfig, player: PlayerId) -> bool {
+    config
+        .players
+        .get(player.0 as usize)
+        .map_or(false, |player| player.kind == PlayerKind::Human)
+}
+
+fn clear_tally(mut tally: ResMut<GameTally>) {
+    *tally = GameTally::default();
+}
+
+fn tally_moves(mut tally: ResMut<GameTally>, mut new_cats: EventReader<NewCat>) {
+    for new_cat in new_cats.iter() {
+        tally.moves += 1;
+        let idx = tally.player(new_cat.player);
+        tally.openings[idx].get_or_insert(new_cat.position);
+        tally.player_moves[idx] += 1;
+    }
+}
+
+fn tally_boops(
+    mut tally: ResMut<GameTally>,
+    mut moves: EventReader<MoveCat>,
+    owners: Query<&PlayerId, With<Meowple>>,
+) {
+    for MoveCat { by, from, to, .. } in moves.iter() {
+        let idx = tally.player(*by);
+        tally.boops_dealt[idx] += 1;
+        if to.is_none() {
+            let Ok(owner) = owners.get(*from) else {
+                continue;
+            };
+            let idx = tally.player(*owner);
+            tally.cats_lost[idx] += 1;
+        }
+    }
+}
+
+/// Add the finished game to the profiles of everyone who played it
+#[instrument(level = "debug", skip_all)]
+fn record_games(
+    config: Res<GameConfig>,
+    settings: Res<Settings>,
+    players: Res<Players>,
+    mut tally: ResMut<GameTally>,
+    mut profiles: ResMut<Profiles>,
+    mut wins: EventReader<WinEvent>,
+) {
+    // a game only ends once
+    let Some(win) = wins.iter().next() else {
+        return;
+    };
+    let palette = settings.palette.colors();
+    let mut recorded = Vec::<&str>::new();
+    for player in &players.players {
+        if !has_profile(&config, player.id) {
+            continue;
+        }
+        let idx = tally.player(player.id);
+        let profile = profiles.get_or_create(&player.name);
+        if let Some(slot) = palette.iter().position(|color| *color == player.color) {
+            profile.color = slot;
+        }
+
+        let stats = &mut profile.stats;
+        // seats with the same profile played one game together
+        if !recorded.contains(&player.name.as_str()) {
+            recorded.push(&player.name);
+            stats.games += 1;
+            let won = win.winner.map(|winner| {
+                players
+                    .players
+                    .iter()
+                    .any(|seat| seat.id == winner && seat.name == player.name)
+            });
+            match won {
+                Some(true) => stats.wins += 1,
+                Some(false) => stats.losses += 1,
+                None => stats.ties += 1,
+            }
+            stats.moves += tally.moves;
+        }
+        stats.boops_dealt += tally.boops_dealt[idx];
+        stats.cats_lost += tally.cats_lost[idx];
+        if let Some(opening) = tally.openings[idx] {
+            *stats.openings.entry((opening.x, opening.y)).or_default() += 1;
+        }
+        debug!(name = ?profile.name, ?stats, "Updated profile");
+    }
+}
+
+fn save_profiles(profiles: Res<Profiles>) {
+    if profiles.is_added() {
+        return;
+    }
+    storage::save(STORAGE_NAME, &*profiles);
+}
+
+fn save_ratings(ratings: Res<Ratings>) {
+    if ratings.is_added() {
+        return;
+    }
+    storage::save(RATINGS_STORAGE_NAME, &*ratings);
+}
@@ -0,0 +1,239 @@
+//! Elo ratings of everyone who played, including the computer player
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{
+    config::PlayerKind,
+    events::WinEvent,
+    players::{Player, PlayerId, Players},
+};
+
+/// Rating of someone who hasn't played yet
+pub const INITIAL_RATING: f32 = 1200.;
+/// Largest change from a game against a single opponent
+const K_FACTOR: f32 = 32.;
+/// Ratings after the last games that are kept for each player
+const HISTORY_LENGTH: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Rated {
+    /// A local player, by name
+    Player(String),
+    /// The computer player, which has a single strength, so there are no
+    /// separate ratings for different levels
+    Computer,
+}
+
+impl Rated {
+    pub fn of(player: &Player) -> Self {
+        match player.kind {
+            PlayerKind::Human => Rated::Player(player.name.clone()),
+            PlayerKind::Computer => Rated::Computer,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rating {
+    pub who: Rated,
+    pub rating: f32,
+    pub games: u32,
+    /// Rating after each of the last games, the oldest first
+    pub history: Vec<f32>,
+}
+
+/// Ratings, stored next to the profiles
+#[derive(Debug, Clone, Default, PartialEq, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Ratings {
+    pub ratings: Vec<Rating>,
+}
+
+impl Ratings {
+    pub fn rating(&self, who: &Rated) -> f32 {
+        self.ratings
+            .iter()
+            .find(|rating| rating.who == *who)
+            .map_or(INITIAL_RATING, |rating| rating.rating)
+    }
+
+    /// Highest rating first
+    pub fn leaderboard(&self) -> Vec<&Rating> {
+        let mut ratings = self.ratings.iter().collect::<Vec<_>>();
+        ratings.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+        ratings
+    }
+
+    /// Rate a finished game between the players in `seats` as one between
+    /// each pair of them, returning the rating of each seat before and after
+    ///
+    /// The winner beat everyone else, the others tied among themselves. In a
+    /// tie everyone tied with everyone. Seats of the same player, like several
+    /// computer players, are rated together and only count one game; a game
+    /// without two different players isn't rated.
+    pub fn rate(&mut self, seats: &[Rated], winner: Option<usize>) -> Vec<(f32, f32)> {
+        let before = seats.iter().map(|who| self.rating(who)).collect::<Vec<_>>();
+        let mut rated: Vec<(&Rated, f32)> = Vec::new();
+        for who in seats {
+            if !rated.iter().any(|(other, _)| *other == who) {
+                rated.push((who, 0.));
+            }
+        }
+        if rated.len() < 2 {
+            return before.iter().map(|rating| (*rating, *rating)).collect();
+        }
+
+        let score = |idx: usize| match winner {
+            Some(winner) if winner == idx => 1.,
+            Some(_) => 0.,
+            None => 0.5,
+        };
+        let k = K_FACTOR / (seats.len() - 1) as f32;
+        for (idx, who) in seats.iter().enumerate() {
+            let mut delta = 0.;
+            for opponent in 0..seats.len() {
+                // nobody plays against themselves
+                if seats[opponent] == *who {
+                    continue;
+                }
+                let actual = match (score(idx), score(opponent)) {
+                    (a, b) if a > b => 1.,
+                    (a, b) if a < b => 0.,
+                    _ => 0.5,
+                };
+                delta += k * (actual - expected_score(before[idx], before[opponent]));
+            }
+            if let Some((_, total)) = rated.iter_mut().find(|(other, _)| *other == who) {
+                *total += delta;
+            }
+        }
+
+        let after = seats
+            .iter()
+            .zip(&before)
+            .map(|(who, before)| {
+                let (_, delta) = rated.iter().find(|(other, _)| *other == who).unwrap();
+                (*before, before + delta)
+            })
+            .collect();
+        for (who, delta) in rated {
+            self.add_game(who.clone(), delta);
+        }
+        after
+    }
+
+    fn add_game(&mut self, who: Rated, change: f32) {
+        let idx = match self.ratings.iter().position(|rating| rating.who == who) {
+            Some(idx) => idx,
+            None => {
+                self.ratings.push(Rating {
+                    who,
+                    rating: INITIAL_RATING,
+                    games: 0,
+                    history: Vec::new(),
+                });
+                self.ratings.len() - 1
+            }
+        };
+        let rating = &mut self.ratings[idx];
+        rating.rating += change;
+        rating.games += 1;
+        rating.history.push(rating.rating);
+        if rating.history.len() > HISTORY_LENGTH {
+            rating.history.remove(0);
+        }
+    }
+}
+
+/// Chance of a player rated `rating` to beat one rated `opponent`
+fn expected_score(rating: f32, opponent: f32) -> f32 {
+    1. / (1. + 10f32.powf((opponent - rating) / 400.))
+}
+
+/// Rating of each player before and after the last game, for the win screen
+#[derive(Debug, Default, Resource)]
+pub struct RatingChanges(pub Vec<RatingChange>);
+
+#[derive(Debug, Clone, Copy)]
+pub struct RatingChange {
+    pub player: PlayerId,
+    pub before: f32,
+    pub after: f32,
+}
+
+/// Rate the finished game and remember the changes for the win screen
+#[instrument(level = "debug", skip_all)]
+pub fn update_ratings(
+    players: Res<Players>,
+    mut ratings: ResMut<Ratings>,
+    mut changes: ResMut<RatingChanges>,
+    mut wins: EventReader<WinEvent>,
+) {
+    // a game only ends once
+    let Some(win) = wins.iter().next() else {
+        return;
+    };
+    let rated = players.players.iter().map(Rated::of).collect::<Vec<_>>();
+    let winner = win.winner.map(|winner| winner.0 as usize);
+    changes.0 = ratings
+        .rate(&rated, winner)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (before, after))| RatingChange {
+            player: PlayerId::new(idx as u8),
+            before,
+            after,
+        })
+        .collect();
+    debug!(changes = ?changes.0, "Updated ratings");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(name: &str) -> Rated {
+        Rated::Player(name.to_string())
+    }
+
+    #[test]
+    fn winner_gains_what_the_loser_loses() {
+        let mut ratings = Ratings::default();
+        let changes = ratings.rate(&[player("Ann"), player("Bo")], Some(0));
+        assert_eq!(changes, vec![(1200., 1216.), (1200., 1184.)]);
+        assert_eq!(ratings.rating(&player("Ann")), 1216.);
+        assert_eq!(ratings.rating(&player("Bo")), 1184.);
+        assert_eq!(ratings.leaderboard()[0].who, player("Ann"));
+    }
+
+    #[test]
+    fn ties_move_ratings_together() {
+        let mut ratings = Ratings::default();
+        ratings.rate(&[player("Ann"), player("Bo")], Some(0));
+        let changes = ratings.rate(&[player("Ann"), player("Bo")], None);
+        assert!(changes[0].1 < changes[0].0);
+        assert!(changes[1].1 > changes[1].0);
+        assert_eq!(ratings.ratings[0].history.len(), 2);
+    }
+
+    #[test]
+    fn computer_seats_count_once() {
+        let mut ratings = Ratings::default();
+        let changes = ratings.rate(&[player("Ann"), Rated::Computer, Rated::Computer], Some(0));
+        assert_eq!(changes[1], changes[2]);
+        assert_eq!(changes[0].1 - 1200., 1200. - changes[1].1);
+        let computer = ratings.leaderboard()[1];
+        assert_eq!(computer.who, Rated::Computer);
+        assert_eq!(computer.games, 1);
+    }
+
+    #[test]
+    fn games_without_opponents_are_not_rated() {
+        let mut ratings = Ratings::default();
+        let changes = ratings.rate(&[Rated::Computer, Rated::Computer], Some(1));
+        assert_eq!(changes, vec![(1200., 1200.); 2]);
+        assert!(ratings.ratings.is_empty());
+    }
+}
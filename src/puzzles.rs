@@ -0,0 +1,240 @@
+//! "Win in N" puzzles: a prepared position where the player to move has to
+//! win within a number of their own moves against a scripted defense.
+//!
+//! Puzzles are stored as RON in `assets/puzzles`, and
+//! `cargo run --bin check_puzzles` makes sure each of them can be solved.
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use hexx::Hex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    rules::{Cat, Game, Outcome, PlayerId, SavedGame},
+    search,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Puzzle {
+    /// Progress is saved under this, so it stays the same when puzzles are
+    /// added or reordered
+    pub id: String,
+    /// Own moves the player has to win within
+    pub moves: u32,
+    /// Position with the player to solve it as the current one
+    pub position: SavedGame,
+    /// Replies of the defending player, in order, played unless searching
+    /// finds one that keeps the player from winning
+    #[serde(default)]
+    pub defense: Vec<((i32, i32), Cat)>,
+}
+
+impl Puzzle {
+    pub fn game(&self) -> Game {
+        self.position.clone().into()
+    }
+
+    pub fn attacker(&self) -> PlayerId {
+        PlayerId::new(self.position.current)
+    }
+
+    /// Defending move number `reply` in `game`, with the attacker having
+    /// `moves_left` moves to win: one that keeps them from winning if there
+    /// is one, else the scripted one
+    pub fn defend(&self, game: &Game, reply: usize, moves_left: u32) -> Option<(Hex, Cat)> {
+        let legal = game.legal_moves();
+        let scripted = self
+            .defense
+            .get(reply)
+            .map(|((x, y), cat)| (Hex::new(*x, *y), *cat))
+            .filter(|scripted| legal.contains(scripted));
+        search::saving_move(game, self.attacker(), moves_left)
+            .or(scripted)
+            .or_else(|| legal.first().copied())
+    }
+
+    /// Moves of both players that win the puzzle, if it can be won
+    pub fn solution(&self) -> Option<Vec<(Hex, Cat)>> {
+        self.solve(&self.game(), self.moves, 0)
+    }
+
+    fn solve(&self, game: &Game, moves_left: u32, reply: usize) -> Option<Vec<(Hex, Cat)>> {
+        if moves_left == 0 {
+            return None;
+        }
+        game.legal_moves().into_iter().find_map(|(hex, cat)| {
+            let next = search::after(game, hex, cat);
+            if next.outcome == Some(Outcome::Winner(self.attacker())) {
+                return Some(vec![(hex, cat)]);
+            }
+            if next.outcome.is_some() || moves_left == 1 {
+                return None;
+            }
+            let defense = self.defend(&next, reply, moves_left - 1)?;
+            let next = search::after(&next, defense.0, defense.1);
+            // boops of the defense can complete the attacker's row
+            if next.outcome == Some(Outcome::Winner(self.attacker())) {
+                return Some(vec![(hex, cat), defense]);
+            }
+            if next.outcome.is_some() || next.current() != self.attacker() {
+                return None;
+            }
+            let rest = self.solve(&next, moves_left - 1, reply + 1)?;
+            Some([vec![(hex, cat), defense], rest].concat())
+        })
+    }
+
+    /// Solution of the puzzle, or what keeps it from being played as intended
+    pub fn check(&self) -> Result<Vec<(Hex, Cat)>, String> {
+        if self.position.inventories.len() != 2 {
+            return Err("puzzles are for two players".to_string());
+        }
+        if self.position.current > 1 {
+            return Err(format!("there is no player {}", self.position.current));
+        }
+        if self.moves == 0 {
+            return Err("needs at least one move".to_string());
+        }
+        let game = self.game();
+        if game.pieces.len() != self.position.pieces.len() {
+            return Err("pieces outside the board or on the same cell".to_string());
+        }
+        if game.outcome.is_some() {
+            return Err("the game is already over".to_string());
+        }
+        self.solution()
+            .ok_or_else(|| format!("cannot be won in {} moves", self.moves))
+    }
+}
+
+/// All puzzles, in the order they are offered
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TypeUuid)]
+#[uuid = "cd125446-b990-4067-8c0c-51473776803c"]
+pub struct PuzzleSet {
+    pub puzzles: Vec<Puzzle>,
+}
+
+impl PuzzleSet {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        ron::from_str(source).map_err(|error| error.to_string())
+    }
+}
+
+#[derive(Default)]
+pub struct PuzzleLoader;
+
+impl AssetLoader for PuzzleLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let source = std::str::from_utf8(bytes)?;
+            let puzzles = PuzzleSet::parse(source).map_err(|error| {
+                bevy::asset::Error::msg(format!("{}: {error}", load_context.path().display()))
+            })?;
+            load_context.set_default_asset(LoadedAsset::new(puzzles));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["puzzles.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Rules;
+
+    /// Green, the attacker, has adult cats on `pieces` and holds only adults,
+    /// orange holds only kittens, which can't boop them away
+    fn puzzle(moves: u32, pieces: &[(i32, i32)], defense: &[(i32, i32)]) -> Puzzle {
+        Puzzle {
+            id: "test".to_string(),
+            moves,
+            position: SavedGame {
+                rules: Rules::Graduation,
+                radius: 3,
+                inventories: vec![(0, 3), (6, 0)],
+                current: 0,
+                pieces: pieces.iter().map(|cell| (*cell, Cat::Adult, 0)).collect(),
+            },
+            defense: defense.iter().map(|cell| (*cell, Cat::Kitten)).collect(),
+        }
+    }
+
+    #[test]
+    fn solves_win_in_one() {
+        let puzzle = puzzle(1, &[(-1, 0), (0, 0)], &[]);
+        let solution = puzzle.check().unwrap();
+        assert_eq!(solution.len(), 1);
+        let (hex, cat) = solution[0];
+        assert_eq!(
+            search::after(&puzzle.game(), hex, cat).outcome,
+            Some(Outcome::Winner(puzzle.attacker()))
+        );
+    }
+
+    #[test]
+    fn solves_win_in_two() {
+        // no cat wins right away, but the first one threatens two rows and
+        // the defense can only block one of them
+        let cats = [(-2, 1), (0, 0)];
+        assert!(puzzle(1, &cats, &[]).check().is_err());
+        assert_eq!(puzzle(2, &cats, &[]).check().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn defense_saves_when_it_can() {
+        let puzzle = puzzle(2, &[(2, 0), (3, 0)], &[(-3, 0)]);
+        let mut game = puzzle.game();
+        game.place(Hex::new(-3, 3), Cat::Adult).unwrap();
+        // (4, 0) is off the board, so only (1, 0) completes the row
+        assert_eq!(
+            puzzle.defend(&game, 0, 1),
+            Some((Hex::new(1, 0), Cat::Kitten))
+        );
+    }
+
+    #[test]
+    fn defense_follows_the_script_when_nothing_saves() {
+        let puzzle = puzzle(2, &[(-1, 0), (0, 0)], &[(-3, 0)]);
+        let mut game = puzzle.game();
+        game.place(Hex::new(0, 3), Cat::Adult).unwrap();
+        assert_eq!(
+            puzzle.defend(&game, 0, 1),
+            Some((Hex::new(-3, 0), Cat::Kitten))
+        );
+        // without a legal scripted reply, any legal move
+        assert_eq!(
+            puzzle.defend(&game, 1, 1),
+            game.legal_moves().first().copied()
+        );
+    }
+
+    #[test]
+    fn broken_puzzles_are_rejected() {
+        let error = |puzzle: Puzzle| puzzle.check().unwrap_err();
+        assert_eq!(
+            error(puzzle(0, &[(-1, 0), (0, 0)], &[])),
+            "needs at least one move"
+        );
+        assert_eq!(
+            error(puzzle(1, &[(-2, 0), (2, 0)], &[])),
+            "cannot be won in 1 moves"
+        );
+        assert_eq!(
+            error(puzzle(1, &[(-1, 0), (0, 0), (1, 0)], &[])),
+            "the game is already over"
+        );
+        let mut three_players = puzzle(1, &[(-1, 0), (0, 0)], &[]);
+        three_players.position.inventories.push((6, 0));
+        assert_eq!(error(three_players), "puzzles are for two players");
+    }
+}
@@ -0,0 +1,526 @@
+//! The game rules without any rendering, shared by the 3D game and the
+//! terminal client (`src/bin/tui.rs`).
+
+use bevy::utils::{HashMap, HashSet};
+use hexx::{shapes, Hex};
+use serde::{Deserialize, Serialize};
+use tracing::trace;
+
+pub use crate::{cats::Cat, config::Rules, players::Inventory, players::PlayerId};
+
+/// Piece that gets booped when a cat is placed next to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Boop {
+    pub from: Hex,
+    /// `None` when the piece is booped off the board
+    pub to: Option<Hex>,
+}
+
+/// Boops caused by putting `new_cat` on `position`, without moving anything
+pub fn boops(
+    position: Hex,
+    new_cat: Cat,
+    on_board: impl Fn(Hex) -> bool,
+    piece_at: impl Fn(Hex) -> Option<Cat>,
+) -> Vec<Boop> {
+    Hex::NEIGHBORS_COORDS
+        .into_iter()
+        .filter_map(|direction| {
+            let from = position + direction;
+            let cat = piece_at(from)?;
+            if !new_cat.can_boop(cat) {
+                return None;
+            }
+            let to = from + direction;
+            if !on_board(to) {
+                return Some(Boop { from, to: None });
+            }
+            if piece_at(to).is_some() {
+                trace!(?to, "Cannot boop to cell with cat");
+                return None;
+            }
+            Some(Boop { from, to: Some(to) })
+        })
+        .collect()
+}
+
+/// All lines of three neighboring cells within `cells`
+///
+/// Longer lines yield every three-cell section, and each line is yielded once
+/// per direction.
+pub fn rows_of_three(cells: &[Hex]) -> impl Iterator<Item = [Hex; 3]> + '_ {
+    cells.iter().flat_map(move |cell| {
+        hexx::Direction::iter().filter_map(move |direction| {
+            let second = cell.neighbor(direction);
+            let third = second.neighbor(direction);
+            (cells.contains(&second) && cells.contains(&third)).then_some([*cell, second, third])
+        })
+    })
+}
+
+/// Kind of cat a player needs three in a row of to win
+pub fn winning_cat(rules: Rules) -> Cat {
+    match rules {
+        Rules::ThreeKittens => Cat::Kitten,
+        Rules::Graduation => Cat::Adult,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Winner(PlayerId),
+    /// Nobody has any pieces left
+    Tie,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    GameOver,
+    NotOnBoard,
+    Occupied,
+    NoPieceLeft,
+}
+
+impl std::fmt::Display for MoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            MoveError::GameOver => "the game is over",
+            MoveError::NotOnBoard => "that cell is not on the board",
+            MoveError::Occupied => "there's already a cat on that cell",
+            MoveError::NoPieceLeft => "no piece of that kind left",
+        })
+    }
+}
+
+/// What a move did, in the order it happened
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MoveReport {
+    pub boops: Vec<Boop>,
+    pub graduated: Vec<(PlayerId, [Hex; 3])>,
+}
+
+/// Complete state of a game
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game {
+    pub rules: Rules,
+    pub radius: u32,
+    cells: HashSet<Hex>,
+    pub pieces: HashMap<Hex, (Cat, PlayerId)>,
+    pub inventories: Vec<Inventory>,
+    current: usize,
+    pub outcome: Option<Outcome>,
+}
+
+impl Game {
+    pub fn new(players: usize, rules: Rules, radius: u32) -> Self {
+        assert!(players > 0, "a game needs players");
+        Self {
+            rules,
+            radius,
+            cells: shapes::hexagon(Hex::ZERO, radius).collect(),
+            pieces: HashMap::default(),
+            inventories: vec![Inventory::default(); players],
+            current: 0,
+            outcome: None,
+        }
+    }
+
+    pub fn current(&self) -> PlayerId {
+        PlayerId::new(self.current as u8)
+    }
+
+    pub fn on_board(&self, hex: Hex) -> bool {
+        self.cells.contains(&hex)
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = Hex> + '_ {
+        self.cells.iter().copied()
+    }
+
+    /// Pieces the current player can place on free cells
+    pub fn legal_moves(&self) -> Vec<(Hex, Cat)> {
+        if self.outcome.is_some() {
+            return Vec::new();
+        }
+        let inventory = &self.inventories[self.current];
+        let mut moves = self
+            .cells()
+            .filter(|hex| !self.pieces.contains_key(hex))
+            .flat_map(|hex| {
+                [Cat::Kitten, Cat::Adult]
+                    .into_iter()
+                    .filter(|cat| inventory.has(*cat))
+                    .map(move |cat| (hex, cat))
+            })
+            .collect::<Vec<_>>();
+        moves.sort_by_key(|(hex, cat)| (hex.x, hex.y, *cat == Cat::Adult));
+        moves
+    }
+
+    pub fn random_move(&self) -> Option<(Hex, Cat)> {
+        let moves = self.legal_moves();
+        (!moves.is_empty()).then(|| moves[fastrand::usize(..moves.len())])
+    }
+
+    /// Current player puts `cat` on `hex`, then it's the next player's turn
+    pub fn place(&mut self, hex: Hex, cat: Cat) -> Result<MoveReport, MoveError> {
+        if self.outcome.is_some() {
+            return Err(MoveError::GameOver);
+        }
+        if !self.on_board(hex) {
+            return Err(MoveError::NotOnBoard);
+        }
+        if self.pieces.contains_key(&hex) {
+            return Err(MoveError::Occupied);
+        }
+        if !self.inventories[self.current].take(cat) {
+            return Err(MoveError::NoPieceLeft);
+        }
+
+        let player = self.current();
+        let boops = boops(
+            hex,
+            cat,
+            |hex| self.on_board(hex),
+            |hex| self.pieces.get(&hex).map(|(cat, _)| *cat),
+        );
+        for Boop { from, to } in &boops {
+            let Some((cat, owner)) = self.pieces.remove(from) else {
+                continue;
+            };
+            match to {
+                Some(to) => {
+                    self.pieces.insert(*to, (cat, owner));
+                }
+                // pieces that fall off go back to their owner
+                None => self.inventories[owner.0 as usize].gain(cat, 1),
+            }
+        }
+        self.pieces.insert(hex, (cat, player));
+
+        // like in the 3D game, rows graduate before checking for a winner
+        let graduated = self.graduate();
+        self.outcome = self.decided();
+        self.next_player();
+
+        Ok(MoveReport { boops, graduated })
+    }
+
+    fn cells_of(&self, player: PlayerId, counts: impl Fn(Cat) -> bool) -> Vec<Hex> {
+        self.pieces
+            .iter()
+            .filter(|(_, (cat, owner))| *owner == player && counts(*cat))
+            .map(|(hex, _)| *hex)
+            .collect()
+    }
+
+    fn players(&self) -> impl Iterator<Item = PlayerId> {
+        (0..self.inventories.len()).map(|idx| PlayerId::new(idx as u8))
+    }
+
+    /// Winner, or a tie when nobody has anything left to place
+    fn decided(&self) -> Option<Outcome> {
+        match self.winner() {
+            Some(winner) => Some(Outcome::Winner(winner)),
+            None if !self.inventories.iter().any(Inventory::has_pieces) => Some(Outcome::Tie),
+            None => None,
+        }
+    }
+
+    fn winner(&self) -> Option<PlayerId> {
+        let winning_cat = winning_cat(self.rules);
+        self.players().find(|player| {
+            rows_of_three(&self.cells_of(*player, |cat| cat == winning_cat))
+                .next()
+                .is_some()
+        })
+    }
+
+    /// Three pieces in a row (at least one of them a kitten) leave the board
+    /// and go back to their owner as adult cats
+    fn graduate(&mut self) -> Vec<(PlayerId, [Hex; 3])> {
+        if self.rules != Rules::Graduation {
+            return Vec::new();
+        }
+        let mut graduated = Vec::new();
+        for player in self.players() {
+            let cells = self.cells_of(player, |_| true);
+            let Some(row) = rows_of_three(&cells)
+                .find(|row| row.iter().any(|hex| self.pieces[hex].0 == Cat::Kitten))
+            else {
+                continue;
+            };
+            for hex in row {
+                self.pieces.remove(&hex);
+            }
+            self.inventories[player.0 as usize].gain(Cat::Adult, row.len() as u8);
+            graduated.push((player, row));
+        }
+        graduated
+    }
+
+    fn next_player(&mut self) {
+        if let Some(next) = next_seat(self.current, self.inventories.len(), |idx| {
+            self.inventories[idx].has_pieces()
+        }) {
+            self.current = next;
+        }
+    }
+}
+
+/// Seat after `current` (out of `count`) whose player can move, going all the
+/// way around back to `current`; players without pieces are skipped
+pub fn next_seat(current: usize, count: usize, can_move: impl Fn(usize) -> bool) -> Option<usize> {
+    (1..=count)
+        .map(|step| (current + step) % count)
+        .find(|idx| can_move(*idx))
+}
+
+/// [`Game`] as it is written to save files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub rules: Rules,
+    pub radius: u32,
+    /// Kittens and cats left for each player
+    pub inventories: Vec<(u8, u8)>,
+    pub current: u8,
+    /// Cell coordinates, piece and owner
+    pub pieces: Vec<((i32, i32), Cat, u8)>,
+}
+
+impl From<&Game> for SavedGame {
+    fn from(game: &Game) -> Self {
+        let mut pieces = game
+            .pieces
+            .iter()
+            .map(|(hex, (cat, owner))| ((hex.x, hex.y), *cat, owner.0))
+            .collect::<Vec<_>>();
+        pieces.sort_by_key(|((x, y), ..)| (*x, *y));
+        Self {
+            rules: game.rules,
+            radius: game.radius,
+            inventories: game
+                .inventories
+                .iter()
+                .map(|inventory| (inventory.kittens(), inventory.cats()))
+                .collect(),
+            current: game.current as u8,
+            pieces,
+        }
+    }
+}
+
+impl From<SavedGame> for Game {
+    fn from(saved: SavedGame) -> Self {
+        let mut game = Game::new(saved.inventories.len().max(1), saved.rules, saved.radius);
+        game.inventories = saved
+            .inventories
+            .iter()
+            .map(|(kittens, cats)| Inventory::new(*kittens, *cats))
+            .collect();
+        game.current = (saved.current as usize).min(game.inventories.len() - 1);
+        game.pieces = saved
+            .pieces
+            .into_iter()
+            .map(|((x, y), cat, owner)| (Hex::new(x, y), (cat, PlayerId::new(owner))))
+            .filter(|(hex, _)| game.on_board(*hex))
+            .collect();
+        game.outcome = game.decided();
+        game
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GREEN: PlayerId = PlayerId(0);
+    const ORANGE: PlayerId = PlayerId(1);
+
+    /// Two players with full hands and `pieces` on a board of radius 3
+    fn setup(rules: Rules, pieces: &[((i32, i32), Cat, u8)]) -> Game {
+        SavedGame {
+            rules,
+            radius: 3,
+            inventories: vec![(6, 0), (6, 0)],
+            current: 0,
+            pieces: pieces.to_vec(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn neighbors_get_booped_away() {
+        let mut game = setup(Rules::ThreeKittens, &[((0, 0), Cat::Kitten, 1)]);
+
+        let report = game.place(Hex::new(1, 0), Cat::Kitten).unwrap();
+        assert_eq!(
+            report.boops,
+            vec![Boop {
+                from: Hex::new(0, 0),
+                to: Some(Hex::new(-1, 0)),
+            }]
+        );
+        assert_eq!(game.pieces.get(&Hex::new(0, 0)), None);
+        assert_eq!(
+            game.pieces.get(&Hex::new(-1, 0)),
+            Some(&(Cat::Kitten, ORANGE))
+        );
+    }
+
+    #[test]
+    fn blocked_pieces_stay() {
+        let mut game = setup(
+            Rules::ThreeKittens,
+            &[((0, 0), Cat::Kitten, 1), ((-1, 0), Cat::Kitten, 1)],
+        );
+        // a piece behind blocks the boop
+        let report = game.place(Hex::new(1, 0), Cat::Kitten).unwrap();
+        assert!(report.boops.is_empty());
+        assert_eq!(
+            game.pieces.get(&Hex::new(0, 0)),
+            Some(&(Cat::Kitten, ORANGE))
+        );
+
+        // kittens are too weak to boop cats
+        let mut game = setup(Rules::Graduation, &[((0, 0), Cat::Adult, 1)]);
+        let report = game.place(Hex::new(1, 0), Cat::Kitten).unwrap();
+        assert!(report.boops.is_empty());
+        assert_eq!(
+            game.pieces.get(&Hex::new(0, 0)),
+            Some(&(Cat::Adult, ORANGE))
+        );
+    }
+
+    #[test]
+    fn pieces_booped_off_come_back_to_their_owner() {
+        let mut game = setup(Rules::Graduation, &[((3, 0), Cat::Adult, 1)]);
+        game.inventories[0] = Inventory::new(5, 1);
+
+        let report = game.place(Hex::new(2, 0), Cat::Adult).unwrap();
+        assert_eq!(
+            report.boops,
+            vec![Boop {
+                from: Hex::new(3, 0),
+                to: None,
+            }]
+        );
+        assert_eq!(game.pieces.get(&Hex::new(3, 0)), None);
+        assert_eq!(game.inventories[1], Inventory::new(6, 1));
+    }
+
+    #[test]
+    fn rows_with_kittens_graduate() {
+        let mut game = setup(
+            Rules::Graduation,
+            &[((-1, 0), Cat::Kitten, 0), ((0, 0), Cat::Kitten, 0)],
+        );
+
+        let report = game.place(Hex::new(1, 0), Cat::Kitten).unwrap();
+        assert_eq!(report.graduated.len(), 1);
+        assert_eq!(report.graduated[0].0, GREEN);
+        assert!(game.pieces.is_empty());
+        assert_eq!(game.inventories[0], Inventory::new(5, 3));
+        assert_eq!(game.outcome, None);
+    }
+
+    #[test]
+    fn three_in_a_row_win() {
+        let mut game = setup(
+            Rules::ThreeKittens,
+            &[((-1, 0), Cat::Kitten, 0), ((0, 0), Cat::Kitten, 0)],
+        );
+
+        game.place(Hex::new(1, 0), Cat::Kitten).unwrap();
+        assert_eq!(game.outcome, Some(Outcome::Winner(GREEN)));
+        assert_eq!(
+            game.place(Hex::new(0, 2), Cat::Kitten),
+            Err(MoveError::GameOver)
+        );
+        assert!(game.legal_moves().is_empty());
+    }
+
+    #[test]
+    fn graduation_comes_before_winning() {
+        // the new cat completes a row of cats, but the kitten next to them is
+        // blocked and graduates together with two of them
+        let mut game = setup(
+            Rules::Graduation,
+            &[
+                ((-2, 0), Cat::Adult, 0),
+                ((-1, 0), Cat::Adult, 0),
+                ((1, 0), Cat::Kitten, 0),
+                ((2, 0), Cat::Kitten, 1),
+            ],
+        );
+        game.inventories[0] = Inventory::new(5, 1);
+
+        let report = game.place(Hex::new(0, 0), Cat::Adult).unwrap();
+        assert!(report.boops.is_empty());
+        assert_eq!(report.graduated.len(), 1);
+        assert_eq!(game.outcome, None);
+        assert_eq!(game.pieces.len(), 2);
+        assert_eq!(game.inventories[0], Inventory::new(5, 3));
+    }
+
+    #[test]
+    fn nothing_left_to_place_is_a_tie() {
+        let mut game = setup(Rules::ThreeKittens, &[]);
+        game.inventories = vec![Inventory::new(1, 0), Inventory::new(0, 0)];
+
+        game.place(Hex::new(0, 0), Cat::Kitten).unwrap();
+        assert_eq!(game.outcome, Some(Outcome::Tie));
+    }
+
+    #[test]
+    fn players_take_turns() {
+        let mut game = setup(Rules::ThreeKittens, &[]);
+        assert_eq!(game.current(), GREEN);
+        game.place(Hex::new(-3, 0), Cat::Kitten).unwrap();
+        assert_eq!(game.current(), ORANGE);
+        assert_eq!(
+            game.place(Hex::new(-3, 0), Cat::Kitten),
+            Err(MoveError::Occupied)
+        );
+        assert_eq!(
+            game.place(Hex::new(0, 0), Cat::Adult),
+            Err(MoveError::NoPieceLeft)
+        );
+        assert_eq!(
+            game.place(Hex::new(4, 0), Cat::Kitten),
+            Err(MoveError::NotOnBoard)
+        );
+        // failed moves don't pass the turn
+        assert_eq!(game.current(), ORANGE);
+        game.place(Hex::new(3, 0), Cat::Kitten).unwrap();
+        assert_eq!(game.current(), GREEN);
+    }
+
+    #[test]
+    fn players_with_empty_hands_are_skipped() {
+        let mut game = Game::new(3, Rules::ThreeKittens, 3);
+        game.inventories[1] = Inventory::new(1, 0);
+
+        game.place(Hex::new(-3, 0), Cat::Kitten).unwrap();
+        assert_eq!(game.current(), PlayerId::new(1));
+        game.place(Hex::new(3, 0), Cat::Kitten).unwrap();
+        // player 1 has nothing left, so it's player 2 and then player 0 again
+        assert_eq!(game.current(), PlayerId::new(2));
+        game.place(Hex::new(0, 3), Cat::Kitten).unwrap();
+        assert_eq!(game.current(), PlayerId::new(0));
+        game.place(Hex::new(0, -3), Cat::Kitten).unwrap();
+        assert_eq!(game.current(), PlayerId::new(2));
+        assert!(!game.legal_moves().is_empty());
+    }
+
+    #[test]
+    fn saved_games_load_the_same_game() {
+        let mut game = setup(Rules::Graduation, &[((0, 0), Cat::Kitten, 1)]);
+        game.place(Hex::new(1, 0), Cat::Kitten).unwrap();
+        game.place(Hex::new(2, -2), Cat::Kitten).unwrap();
+
+        let saved = ron::to_string(&SavedGame::from(&game)).unwrap();
+        let loaded = Game::from(ron::from_str::<SavedGame>(&saved).unwrap());
+        assert_eq!(loaded, game);
+    }
+}
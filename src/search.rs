@@ -0,0 +1,284 @@
+//! Looking ahead in a [`Game`]: moves that force a win, used to play and
+//! check puzzles, and scores of positions for the analysis board.
+
+use std::{
+    cmp::Reverse,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use hexx::Hex;
+
+use crate::rules::{winning_cat, Cat, Game, Outcome, PlayerId, Rules};
+
+/// Score of a won game, for the winner
+pub const WIN_SCORE: i32 = 10_000;
+/// Two of the winning pieces in a row with a free cell to complete it
+const THREAT_SCORE: i32 = 20;
+/// Two own pieces in a row with a free cell to graduate them
+const GRADUATION_SCORE: i32 = 5;
+/// Adult cat on the board or in hand
+const CAT_SCORE: i32 = 6;
+
+/// Move of the current player that wins within `moves` of their own moves
+/// whatever the others play, if there is one
+pub fn forced_win(game: &Game, moves: u32) -> Option<(Hex, Cat)> {
+    if moves == 0 {
+        return None;
+    }
+    let player = game.current();
+    game.legal_moves()
+        .into_iter()
+        .find(|(hex, cat)| wins_after(&after(game, *hex, *cat), player, moves - 1))
+}
+
+/// Reply of the current player after which `attacker` can't force a win
+/// within `moves` of their own moves, if there is one
+pub fn saving_move(game: &Game, attacker: PlayerId, moves: u32) -> Option<(Hex, Cat)> {
+    game.legal_moves()
+        .into_iter()
+        .find(|(hex, cat)| !wins_after(&after(game, *hex, *cat), attacker, moves))
+}
+
+/// Whether `player` has won, or wins within `moves` more of their own moves
+/// whatever the others play
+pub fn wins_after(game: &Game, player: PlayerId, moves: u32) -> bool {
+    match game.outcome {
+        Some(Outcome::Winner(winner)) => return winner == player,
+        Some(Outcome::Tie) => return false,
+        None => {}
+    }
+    if game.current() == player {
+        return forced_win(game, moves).is_some();
+    }
+    if moves == 0 {
+        return false;
+    }
+    let replies = game.legal_moves();
+    !replies.is_empty()
+        && replies
+            .into_iter()
+            .all(|(hex, cat)| wins_after(&after(game, hex, cat), player, moves))
+}
+
+/// `game` after the current player puts `cat` on `hex`, which has to be a
+/// legal move
+pub fn after(game: &Game, hex: Hex, cat: Cat) -> Game {
+    let mut next = game.clone();
+    next.place(hex, cat).expect("legal moves can be made");
+    next
+}
+
+/// Moves of the current player with their scores, best first
+#[derive(Debug, Clone, Default)]
+pub struct Analysis {
+    /// Score of the position for the current player, positive when they are
+    /// ahead
+    pub score: i32,
+    pub moves: Vec<((Hex, Cat), i32)>,
+}
+
+/// Score every move of the current player looking `depth` moves ahead,
+/// assuming everyone else plays against them; `None` once `cancel` is set
+pub fn analyse(game: &Game, depth: u32, cancel: &AtomicBool) -> Option<Analysis> {
+    let player = game.current();
+    let mut moves = Vec::new();
+    for (hex, cat) in game.legal_moves() {
+        let next = after(game, hex, cat);
+        let score = paranoid(
+            &next,
+            player,
+            depth.saturating_sub(1),
+            -WIN_SCORE * 2,
+            WIN_SCORE * 2,
+            cancel,
+        );
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        moves.push(((hex, cat), score));
+    }
+    // stable, so equal moves stay in board order
+    moves.sort_by_key(|(_, score)| Reverse(*score));
+    Some(Analysis {
+        score: moves
+            .first()
+            .map_or_else(|| evaluate(game, player), |(_, score)| *score),
+        moves,
+    })
+}
+
+/// Minimax with alpha-beta pruning where `player` maximizes and all others
+/// minimize; quicker wins score higher. Gives up with a meaningless score once
+/// `cancel` is set.
+fn paranoid(
+    game: &Game,
+    player: PlayerId,
+    depth: u32,
+    mut alpha: i32,
+    mut beta: i32,
+    cancel: &AtomicBool,
+) -> i32 {
+    if cancel.load(Ordering::Relaxed) {
+        return 0;
+    }
+    let moves = game.legal_moves();
+    if game.outcome.is_some() || depth == 0 || moves.is_empty() {
+        // decided sooner means more depth left
+        return match evaluate(game, player) {
+            WIN_SCORE => WIN_SCORE + depth as i32,
+            score if score == -WIN_SCORE => score - depth as i32,
+            score => score,
+        };
+    }
+    let maximizing = game.current() == player;
+    let mut best = if maximizing { i32::MIN } else { i32::MAX };
+    for (hex, cat) in moves {
+        let score = paranoid(
+            &after(game, hex, cat),
+            player,
+            depth - 1,
+            alpha,
+            beta,
+            cancel,
+        );
+        if maximizing {
+            best = best.max(score);
+            alpha = alpha.max(best);
+        } else {
+            best = best.min(score);
+            beta = beta.min(best);
+        }
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+/// Score of `game` for `player` without looking ahead: their prospects
+/// against the best of the others
+pub fn evaluate(game: &Game, player: PlayerId) -> i32 {
+    match game.outcome {
+        Some(Outcome::Winner(winner)) if winner == player => return WIN_SCORE,
+        Some(Outcome::Winner(_)) => return -WIN_SCORE,
+        Some(Outcome::Tie) => return 0,
+        None => {}
+    }
+    let others = (0..game.inventories.len())
+        .map(|idx| PlayerId::new(idx as u8))
+        .filter(|other| *other != player)
+        .map(|other| prospects(game, other))
+        .max()
+        .unwrap_or_default();
+    prospects(game, player) - others
+}
+
+fn prospects(game: &Game, player: PlayerId) -> i32 {
+    let winning = winning_cat(game.rules);
+    let mut score = 0;
+    // each line once, in three of the six directions
+    for start in game.cells() {
+        for direction in &Hex::NEIGHBORS_COORDS[..3] {
+            let line = [start, start + *direction, start + *direction + *direction];
+            if !line.iter().all(|hex| game.on_board(*hex)) {
+                continue;
+            }
+            let pieces = line.map(|hex| game.pieces.get(&hex));
+            let free = pieces.iter().filter(|piece| piece.is_none()).count();
+            let own = pieces
+                .iter()
+                .flatten()
+                .filter(|(_, owner)| *owner == player)
+                .collect::<Vec<_>>();
+            if free != 1 || own.len() != 2 {
+                continue;
+            }
+            if own.iter().all(|(cat, _)| *cat == winning) {
+                score += THREAT_SCORE;
+            } else if game.rules == Rules::Graduation {
+                score += GRADUATION_SCORE;
+            }
+        }
+    }
+    for (hex, (cat, owner)) in &game.pieces {
+        if *owner != player {
+            continue;
+        }
+        // pieces near the edge are easily booped off
+        score += game.radius as i32 - hex.distance_to(Hex::ZERO);
+        if *cat == Cat::Adult {
+            score += CAT_SCORE;
+        }
+    }
+    score + game.inventories[player.0 as usize].cats() as i32 * CAT_SCORE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::SavedGame;
+
+    const GREEN: PlayerId = PlayerId(0);
+
+    /// Green has adult cats on `pieces` and holds only adults, orange holds
+    /// only kittens, which can't boop them away
+    fn setup(current: u8, pieces: &[(i32, i32)]) -> Game {
+        SavedGame {
+            rules: Rules::Graduation,
+            radius: 3,
+            inventories: vec![(0, 2), (6, 0)],
+            current,
+            pieces: pieces
+                .iter()
+                .map(|cell| (*cell, Cat::Adult, GREEN.0))
+                .collect(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn finds_win_in_one() {
+        let game = setup(0, &[(-1, 0), (0, 0)]);
+        let (hex, cat) = forced_win(&game, 1).unwrap();
+        assert_eq!(after(&game, hex, cat).outcome, Some(Outcome::Winner(GREEN)));
+        assert!(wins_after(&game, GREEN, 1));
+        assert_eq!(forced_win(&game, 0), None);
+    }
+
+    #[test]
+    fn no_win_without_a_row() {
+        let game = setup(0, &[(-2, 0), (2, 0)]);
+        assert_eq!(forced_win(&game, 1), None);
+        assert!(!wins_after(&game, GREEN, 1));
+    }
+
+    #[test]
+    fn blocking_the_only_gap_saves() {
+        // (4, 0) is off the board, so only (1, 0) completes the row
+        let game = setup(1, &[(2, 0), (3, 0)]);
+        assert!(!wins_after(&game, GREEN, 0));
+        assert_eq!(
+            saving_move(&game, GREEN, 1),
+            Some((Hex::new(1, 0), Cat::Kitten))
+        );
+    }
+
+    #[test]
+    fn open_rows_cannot_be_saved() {
+        let game = setup(1, &[(-1, 0), (0, 0)]);
+        assert_eq!(saving_move(&game, GREEN, 1), None);
+        assert!(wins_after(&game, GREEN, 1));
+    }
+
+    #[test]
+    fn analysis_ranks_winning_moves_first() {
+        let game = setup(0, &[(-1, 0), (0, 0)]);
+        let analysis = analyse(&game, 2, &AtomicBool::new(false)).unwrap();
+        assert!(analysis.score > WIN_SCORE);
+        let ((hex, cat), _) = analysis.moves[0];
+        assert_eq!(after(&game, hex, cat).outcome, Some(Outcome::Winner(GREEN)));
+        assert_eq!(analysis.moves.len(), game.legal_moves().len());
+
+        assert!(analyse(&game, 2, &AtomicBool::new(true)).is_none());
+    }
+}
@@ -0,0 +1,392 @@
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowMode},
+};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{
+    config::{GameConfig, Palette},
+    locale::Language,
+    players::Players,
+    storage,
+};
+
+/// Name the settings are stored under, see [`storage`]
+const STORAGE_NAME: &str = "settings";
+
+/// Window sizes to choose from in the settings menu
+pub const RESOLUTIONS: [(f32, f32); 4] =
+    [(800., 600.), (1280., 720.), (1600., 900.), (1920., 1080.)];
+
+pub const MIN_ANIMATION_SPEED: f32 = 0.5;
+pub const MAX_ANIMATION_SPEED: f32 = 3.0;
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Settings>();
+        app.insert_resource(storage::load::<Settings>(STORAGE_NAME).unwrap_or_default());
+
+        app.add_systems((
+            apply_window_settings.run_if(resource_changed::<Settings>()),
+            apply_palette.run_if(resource_changed::<Settings>()),
+            save_settings.run_if(resource_changed::<Settings>()),
+        ));
+    }
+}
+
+/// User preferences, stored between sessions
+#[derive(Debug, Clone, PartialEq, Resource, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Settings {
+    /// Sound effect volume from 0 to 1
+    pub volume: f32,
+    /// Music volume from 0 to 1
+    pub music_volume: f32,
+    /// Silences both sound effects and music
+    pub muted: bool,
+    pub fullscreen: bool,
+    /// Window size when not in fullscreen
+    pub resolution: (f32, f32),
+    /// Factor for how fast animations play
+    pub animation_speed: f32,
+    pub palette: Palette,
+    /// Show a shape per player above each cat
+    pub piece_markers: bool,
+    pub language: Language,
+    pub keys: KeyBindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            music_volume: 0.5,
+            muted: false,
+            fullscreen: false,
+            resolution: RESOLUTIONS[0],
+            animation_speed: 1.0,
+            palette: Palette::default(),
+            piece_markers: false,
+            language: Language::default(),
+            keys: KeyBindings::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn cycle_resolution(&mut self) {
+        let idx = RESOLUTIONS
+            .iter()
+            .position(|r| *r == self.resolution)
+            .map_or(0, |idx| (idx + 1) % RESOLUTIONS.len());
+        self.resolution = RESOLUTIONS[idx];
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Reflect, FromReflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub restart: KeyCode,
+    pub pause: KeyCode,
+    pub switch_piece: KeyCode,
+    /// Place a cat on the cell under the keyboard cursor
+    pub confirm: KeyCode,
+    /// Hide the keyboard cursor
+    pub cancel: KeyCode,
+    /// Cycle through the camera presets
+    pub camera: KeyCode,
+    /// Show cells where lines can be completed
+    pub overlay: KeyCode,
+    pub mute: KeyCode,
+    /// Show the log of moves
+    pub log: KeyCode,
+    /// Announce all pieces on the board
+    pub describe: KeyCode,
+    /// Type in the coordinates of a cell to place a cat on
+    pub enter_move: KeyCode,
+    /// Show the list of moves
+    pub history: KeyCode,
+    /// Move the keyboard cursor, each direction with a second key
+    pub cursor_up: KeyCode,
+    pub cursor_up_alt: KeyCode,
+    pub cursor_down: KeyCode,
+    pub cursor_down_alt: KeyCode,
+    pub cursor_left: KeyCode,
+    pub cursor_left_alt: KeyCode,
+    pub cursor_right: KeyCode,
+    pub cursor_right_alt: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            restart: KeyCode::R,
+            pause: KeyCode::Escape,
+            switch_piece: KeyCode::C,
+            confirm: KeyCode::Space,
+            cancel: KeyCode::Back,
+            camera: KeyCode::V,
+            overlay: KeyCode::T,
+            mute: KeyCode::M,
+            log: KeyCode::L,
+            describe: KeyCode::B,
+            enter_move: KeyCode::Return,
+            history: KeyCode::H,
+            cursor_up: KeyCode::Up,
+            cursor_up_alt: KeyCode::W,
+            cursor_down: KeyCode::Down,
+            cursor_down_alt: KeyCode::S,
+            cursor_left: KeyCode::Left,
+            cursor_left_alt: KeyCode::A,
+            cursor_right: KeyCode::Right,
+            cursor_right_alt: KeyCode::D,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAction {
+    Restart,
+    Pause,
+    SwitchPiece,
+    Confirm,
+    Cancel,
+    Camera,
+    Overlay,
+    Mute,
+    Log,
+    Describe,
+    EnterMove,
+    History,
+    CursorUp,
+    CursorUpAlt,
+    CursorDown,
+    CursorDownAlt,
+    CursorLeft,
+    CursorLeftAlt,
+    CursorRight,
+    CursorRightAlt,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 20] = [
+        KeyAction::Restart,
+        KeyAction::Pause,
+        KeyAction::SwitchPiece,
+        KeyAction::Confirm,
+        KeyAction::Cancel,
+        KeyAction::Camera,
+        KeyAction::Overlay,
+        KeyAction::Mute,
+        KeyAction::Log,
+        KeyAction::Describe,
+        KeyAction::EnterMove,
+        KeyAction::History,
+        KeyAction::CursorUp,
+        KeyAction::CursorUpAlt,
+        KeyAction::CursorDown,
+        KeyAction::CursorDownAlt,
+        KeyAction::CursorLeft,
+        KeyAction::CursorLeftAlt,
+        KeyAction::CursorRight,
+        KeyAction::CursorRightAlt,
+    ];
+
+    /// Translation id of the action's name
+    pub fn label_id(&self) -> &'static str {
+        match self {
+            KeyAction::Restart => "key-restart",
+            KeyAction::Pause => "key-pause",
+            KeyAction::SwitchPiece => "key-switch-piece",
+            KeyAction::Confirm => "key-confirm",
+            KeyAction::Cancel => "key-cancel",
+            KeyAction::Camera => "key-camera",
+            KeyAction::Overlay => "key-overlay",
+            KeyAction::Mute => "key-mute",
+            KeyAction::Log => "key-log",
+            KeyAction::Describe => "key-describe",
+            KeyAction::EnterMove => "key-enter-move",
+            KeyAction::History => "key-history",
+            KeyAction::CursorUp | KeyAction::CursorUpAlt => "key-cursor-up",
+            KeyAction::CursorDown | KeyAction::CursorDownAlt => "key-cursor-down",
+            KeyAction::CursorLeft | KeyAction::CursorLeftAlt => "key-cursor-left",
+            KeyAction::CursorRight | KeyAction::CursorRightAlt => "key-cursor-right",
+        }
+    }
+
+    /// Second key of the same action, shown next to the first one
+    pub fn alternative(&self) -> Option<KeyAction> {
+        match self {
+            KeyAction::CursorUp => Some(KeyAction::CursorUpAlt),
+            KeyAction::CursorDown => Some(KeyAction::CursorDownAlt),
+            KeyAction::CursorLeft => Some(KeyAction::CursorLeftAlt),
+            KeyAction::CursorRight => Some(KeyAction::CursorRightAlt),
+            _ => None,
+        }
+    }
+
+    pub fn is_alternative(&self) -> bool {
+        matches!(
+            self,
+            KeyAction::CursorUpAlt
+                | KeyAction::CursorDownAlt
+                | KeyAction::CursorLeftAlt
+                | KeyAction::CursorRightAlt
+        )
+    }
+}
+
+impl KeyBindings {
+    pub fn get(&self, action: KeyAction) -> KeyCode {
+        match action {
+            KeyAction::Restart => self.restart,
+            KeyAction::Pause => self.pause,
+            KeyAction::SwitchPiece => self.switch_piece,
+            KeyAction::Confirm => self.confirm,
+            KeyAction::Cancel => self.cancel,
+            KeyAction::Camera => self.camera,
+            KeyAction::Overlay => self.overlay,
+            KeyAction::Mute => self.mute,
+            KeyAction::Log => self.log,
+            KeyAction::Describe => self.describe,
+            KeyAction::EnterMove => self.enter_move,
+            KeyAction::History => self.history,
+            KeyAction::CursorUp => self.cursor_up,
+            KeyAction::CursorUpAlt => self.cursor_up_alt,
+            KeyAction::CursorDown => self.cursor_down,
+            KeyAction::CursorDownAlt => self.cursor_down_alt,
+            KeyAction::CursorLeft => self.cursor_left,
+            KeyAction::CursorLeftAlt => self.cursor_left_alt,
+            KeyAction::CursorRight => self.cursor_right,
+            KeyAction::CursorRightAlt => self.cursor_right_alt,
+        }
+    }
+
+    /// Bind `key` to `action`; an action that already had `key` gets the
+    /// previous key of `action` instead, so keys never do two things
+    pub fn set(&mut self, action: KeyAction, key: KeyCode) {
+        let previous = self.get(action);
+        if let Some(other) = KeyAction::ALL
+            .into_iter()
+            .find(|other| *other != action && self.get(*other) == key)
+        {
+            self.bind(other, previous);
+        }
+        self.bind(action, key);
+    }
+
+    fn bind(&mut self, action: KeyAction, key: KeyCode) {
+        match action {
+            KeyAction::Restart => self.restart = key,
+            KeyAction::Pause => self.pause = key,
+            KeyAction::SwitchPiece => self.switch_piece = key,
+            KeyAction::Confirm => self.confirm = key,
+            KeyAction::Cancel => self.cancel = key,
+            KeyAction::Camera => self.camera = key,
+            KeyAction::Overlay => self.overlay = key,
+            KeyAction::Mute => self.mute = key,
+            KeyAction::Log => self.log = key,
+            KeyAction::Describe => self.describe = key,
+            KeyAction::EnterMove => self.enter_move = key,
+            KeyAction::History => self.history = key,
+            KeyAction::CursorUp => self.cursor_up = key,
+            KeyAction::CursorUpAlt => self.cursor_up_alt = key,
+            KeyAction::CursorDown => self.cursor_down = key,
+            KeyAction::CursorDownAlt => self.cursor_down_alt = key,
+            KeyAction::CursorLeft => self.cursor_left = key,
+            KeyAction::CursorLeftAlt => self.cursor_left_alt = key,
+            KeyAction::CursorRight => self.cursor_right = key,
+            KeyAction::CursorRightAlt => self.cursor_right_alt = key,
+        }
+    }
+}
+
+fn apply_window_settings(
+    settings: Res<Settings>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+
+    let mode = if settings.fullscreen {
+        WindowMode::BorderlessFullscreen
+    } else {
+        WindowMode::Windowed
+    };
+    if window.mode != mode {
+        window.mode = mode;
+    }
+
+    // the canvas fills the browser window instead
+    if cfg!(target_arch = "wasm32") {
+        return;
+    }
+    let (width, height) = settings.resolution;
+    if window.resolution.width() != width || window.resolution.height() != height {
+        window.resolution.set(width, height);
+    }
+}
+
+/// Swap player colors over to the selected palette
+fn apply_palette(
+    settings: Res<Settings>,
+    mut applied: Local<Palette>,
+    mut config: ResMut<GameConfig>,
+    mut players: ResMut<Players>,
+) {
+    if settings.palette == *applied {
+        return;
+    }
+
+    for player in config.players.iter_mut() {
+        player.color = applied.recolor(player.color, settings.palette);
+    }
+    for player in players.players.iter_mut() {
+        player.color = applied.recolor(player.color, settings.palette);
+    }
+    *applied = settings.palette;
+}
+
+#[instrument(level = "debug", skip_all)]
+fn save_settings(settings: Res<Settings>) {
+    if settings.is_added() {
+        return;
+    }
+    storage::save(STORAGE_NAME, &*settings);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn taken_keys_are_swapped() {
+        let mut keys = KeyBindings::default();
+        keys.set(KeyAction::Pause, KeyCode::R);
+        assert_eq!(keys.pause, KeyCode::R);
+        assert_eq!(keys.restart, KeyCode::Escape);
+
+        keys.set(KeyAction::SwitchPiece, KeyCode::F1);
+        assert_eq!(keys.switch_piece, KeyCode::F1);
+        assert_eq!(keys.restart, KeyCode::Escape);
+
+        keys.set(KeyAction::CursorUpAlt, KeyCode::Down);
+        assert_eq!(keys.cursor_up_alt, KeyCode::Down);
+        assert_eq!(keys.cursor_down, KeyCode::W);
+    }
+
+    #[test]
+    fn default_keys_do_one_thing() {
+        let keys = KeyBindings::default();
+        for (idx, action) in KeyAction::ALL.into_iter().enumerate() {
+            for other in &KeyAction::ALL[idx + 1..] {
+                assert_ne!(keys.get(action), keys.get(*other), "{action:?} {other:?}");
+            }
+        }
+    }
+}
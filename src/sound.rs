@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use tracing::instrument;
+
+use crate::{
+    grid::MapSettings, loading::AudioAssets, menu::key_bindings_active, settings::Settings,
+    GameState,
+};
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlaySound>();
+        app.add_systems((
+            toggle_mute.run_if(key_bindings_active),
+            play_sounds.run_if(not(in_state(GameState::Loading))),
+        ));
+    }
+}
+
+/// Sound effects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cue {
+    Place,
+    /// Higher pitched the more cats get booped at once
+    Boop {
+        cats: usize,
+    },
+    FallOff,
+    Graduate,
+    Win,
+    Tie,
+    /// A player's clock is about to run out
+    TooSlow,
+}
+
+#[derive(Debug)]
+pub struct PlaySound {
+    pub cue: Cue,
+    /// Where on the board the sound comes from, played centered if `None`
+    pub position: Option<Vec3>,
+}
+
+impl From<Cue> for PlaySound {
+    fn from(cue: Cue) -> Self {
+        PlaySound {
+            cue,
+            position: None,
+        }
+    }
+}
+
+fn toggle_mute(keys: Res<Input<KeyCode>>, mut settings: ResMut<Settings>) {
+    if keys.just_pressed(settings.keys.mute) {
+        settings.muted = !settings.muted;
+        info!(muted = settings.muted, "Toggled sound");
+    }
+}
+
+#[instrument(level = "debug", skip_all)]
+fn play_sounds(
+    audio: Res<Audio>,
+    sounds: Res<AudioAssets>,
+    settings: Res<Settings>,
+    map_settings: Res<MapSettings>,
+    mut events: EventReader<PlaySound>,
+    cameras: Query<&Transform, With<Camera3d>>,
+) {
+    if settings.muted {
+        events.clear();
+        return;
+    }
+    let camera = cameras.get_single().copied().unwrap_or_default();
+
+    for PlaySound { cue, position } in events.iter() {
+        debug!(?cue, ?position, "Playing sound");
+        let (sound, speed) = match *cue {
+            Cue::Place => (&sounds.place, 0.95 + fastrand::f32() * 0.1),
+            Cue::Boop { cats } => (&sounds.boop, 1. + 0.12 * cats.saturating_sub(1) as f32),
+            Cue::FallOff => (&sounds.fall, 1.),
+            Cue::Graduate => (&sounds.graduate, 1.),
+            Cue::Win => (&sounds.win, 1.),
+            Cue::Tie => (&sounds.tie, 1.),
+            Cue::TooSlow => (&sounds.too_slow, 1.),
+        };
+        let playback = PlaybackSettings::ONCE
+            .with_volume(settings.volume)
+            .with_speed(speed);
+
+        let Some(position) = position else {
+            audio.play_with_settings(sound.clone(), playback);
+            continue;
+        };
+        // Spatial audio gets quieter with the squared distance, so work in
+        // board-sized units: ears half a unit left and right of the board
+        // center, facing the way the camera does, and sounds at most half a
+        // unit away.
+        let listener = Transform::from_rotation(camera.rotation);
+        let emitter = *position / map_settings.board_radius() * 0.5;
+        audio.play_spatial_with_settings(sound.clone(), playback, listener, 1., emitter);
+    }
+}
@@ -0,0 +1,79 @@
+//! Settings and player profiles are stored as RON in the user's config
+//! directory, or in `localStorage` when running in the browser.
+
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path(name: &str) -> Option<std::path::PathBuf> {
+    Some(dirs::config_dir()?.join("boop").join(format!("{name}.ron")))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let path = path(name)?;
+    let content = std::fs::read_to_string(&path).ok()?;
+    ron::from_str(&content)
+        .map_err(|error| warn!(?error, ?path, "Cannot read {name}"))
+        .ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save<T: Serialize>(name: &str, value: &T) {
+    let Some(path) = path(name) else {
+        warn!("No config directory to save {name} to");
+        return;
+    };
+    let content = match ron::ser::to_string_pretty(value, default()) {
+        Ok(x) => x,
+        Err(error) => {
+            error!(?error, "Cannot serialize {name}");
+            return;
+        }
+    };
+    if let Some(dir) = path.parent() {
+        if let Err(error) = std::fs::create_dir_all(dir) {
+            error!(?error, ?dir, "Cannot create config directory");
+            return;
+        }
+    }
+    if let Err(error) = std::fs::write(&path, content) {
+        error!(?error, ?path, "Cannot save {name}");
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn storage_key(name: &str) -> String {
+    format!("boop-{name}")
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn load<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let content = local_storage()?.get_item(&storage_key(name)).ok()??;
+    ron::from_str(&content)
+        .map_err(|error| warn!(?error, "Cannot read {name}"))
+        .ok()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save<T: Serialize>(name: &str, value: &T) {
+    let Some(storage) = local_storage() else {
+        warn!("No localStorage to save {name} to");
+        return;
+    };
+    let content = match ron::to_string(value) {
+        Ok(x) => x,
+        Err(error) => {
+            error!(?error, "Cannot serialize {name}");
+            return;
+        }
+    };
+    if let Err(error) = storage.set_item(&storage_key(name), &content) {
+        error!(?error, "Cannot save {name}");
+    }
+}